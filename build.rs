@@ -18,7 +18,8 @@ fn days(input_dir: &str) -> io::Result<Vec<u32>> {
 fn gen_solutions_mod<P: AsRef<Path>>(p: P, days: &[u32]) -> io::Result<()> {
     let mut f = File::create(p)?;
     writeln!(f, "// DO NOT EDIT THIS FILE")?;
-    writeln!(f, "use crate::solver::Solver;")?;
+    writeln!(f, "#[allow(unused_imports)]")?;
+    writeln!(f, "use crate::solver::{{Solver, ZeroCopySolver}};")?;
     writeln!(f)?;
     for day in days {
         writeln!(f, "mod day{0:02};", day)?;
@@ -26,11 +27,15 @@ fn gen_solutions_mod<P: AsRef<Path>>(p: P, days: &[u32]) -> io::Result<()> {
     writeln!(f)?;
     writeln!(
         f,
-        "pub fn exec_day(day: i32) {{
+        "pub fn exec_day(day: i32, debug: bool) {{
     match day {{"
     )?;
     for day in days {
-        writeln!(f, "        {0} => day{0:02}::Problem {{}}.solve(day),", day)?;
+        writeln!(
+            f,
+            "        {0} => day{0:02}::Problem {{}}.solve(day, debug),",
+            day
+        )?;
     }
     writeln!(
         f,
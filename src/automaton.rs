@@ -0,0 +1,281 @@
+use crate::bitgrid::BitGrid;
+use crate::cycle::{find_cycle_hashed, Cycle};
+use crate::grid::{Connectivity, Edge, Grid};
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+#[allow(unused)]
+impl<T> Grid<T> {
+    // computes the next generation of the grid, every cell being updated simultaneously
+    pub fn step<F>(&self, rule: F) -> Grid<T>
+    where
+        T: Clone,
+        F: FnMut(&Grid<T>, (usize, usize), &T) -> T,
+    {
+        let mut next = self.clone();
+        self.step_into(&mut next, rule);
+        next
+    }
+
+    // same as `step`, but writes the result into an existing grid of the same size
+    pub fn step_into<F>(&self, next: &mut Grid<T>, mut rule: F)
+    where
+        F: FnMut(&Grid<T>, (usize, usize), &T) -> T,
+    {
+        assert_eq!((self.w, self.h), (next.w, next.h), "grid sizes differ");
+
+        let w = self.w;
+        for (i, (cell, out)) in self.as_slice().iter().zip(next.as_slice_mut()).enumerate() {
+            *out = rule(self, (i % w, i / w), cell);
+        }
+    }
+
+    // runs a cascading update in place:
+    // `update` is applied once to every cell, and every cell for which it returns true triggers.
+    // `propagate` is then applied to the neighbours of each triggered cell which haven't triggered
+    // yet, and these trigger in turn when it returns true.
+    // Each cell triggers at most once per step, the coordinates of triggered cells are returned
+    // in triggering order.
+    pub fn cascade<U, P>(
+        &mut self,
+        connectivity: Connectivity,
+        mut update: U,
+        mut propagate: P,
+    ) -> Vec<(usize, usize)>
+    where
+        U: FnMut(&mut T) -> bool,
+        P: FnMut(&mut T) -> bool,
+    {
+        let mut triggered = BitGrid::new(self.w, self.h);
+        let mut order = vec![];
+        let mut queue = VecDeque::new();
+
+        // 1. update all cells
+        let w = self.w;
+        for (i, cell) in self.as_slice_mut().iter_mut().enumerate() {
+            if update(cell) {
+                let c = (i % w, i / w);
+                triggered.set(&c, true);
+                order.push(c);
+                queue.push_back(c);
+            }
+        }
+
+        // 2. propagate to neighbours until nothing triggers anymore
+        while let Some(c) = queue.pop_front() {
+            for n in self.neighbours_coords_with(c, connectivity, Edge::Skip) {
+                if triggered.get(&n) {
+                    continue;
+                }
+
+                if let Some(cell) = self.get_mut(n) {
+                    if propagate(cell) {
+                        triggered.set(&n, true);
+                        order.push(n);
+                        queue.push_back(n);
+                    }
+                }
+            }
+        }
+
+        order
+    }
+
+    pub fn all(&self, f: impl Fn(&T) -> bool) -> bool {
+        self.as_slice().iter().all(f)
+    }
+}
+
+// a grid evolving over time, with a back buffer for simultaneous updates
+#[derive(Clone, Debug)]
+pub struct Automaton<T> {
+    grid: Grid<T>,
+    back: Grid<T>,
+    generation: usize,
+}
+
+#[allow(unused)]
+impl<T> Automaton<T>
+where
+    T: Clone,
+{
+    pub fn new(grid: Grid<T>) -> Self {
+        Self {
+            back: grid.clone(),
+            grid,
+            generation: 0,
+        }
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    // simultaneous update of all cells, returns true if any cell changed
+    pub fn step<F>(&mut self, rule: F) -> bool
+    where
+        T: PartialEq,
+        F: FnMut(&Grid<T>, (usize, usize), &T) -> T,
+    {
+        self.grid.step_into(&mut self.back, rule);
+        std::mem::swap(&mut self.grid, &mut self.back);
+        self.generation += 1;
+
+        self.grid.as_slice() != self.back.as_slice()
+    }
+
+    // cascading update, see `Grid::cascade`, after which `settle` is applied to every triggered
+    // cell; the whole counts as one generation
+    pub fn cascade<U, P, S>(
+        &mut self,
+        connectivity: Connectivity,
+        update: U,
+        propagate: P,
+        mut settle: S,
+    ) -> Vec<(usize, usize)>
+    where
+        U: FnMut(&mut T) -> bool,
+        P: FnMut(&mut T) -> bool,
+        S: FnMut(&mut T),
+    {
+        let triggered = self.grid.cascade(connectivity, update, propagate);
+        for &c in &triggered {
+            if let Some(cell) = self.grid.get_mut(c) {
+                settle(cell);
+            }
+        }
+        self.generation += 1;
+
+        triggered
+    }
+
+    // arbitrary in-place update counting as one generation
+    pub fn advance<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut Grid<T>) -> R,
+    {
+        self.generation += 1;
+        f(&mut self.grid)
+    }
+
+    // advances with `step` until `done` holds, returns the generation at which it happened
+    pub fn run_until<S, D>(&mut self, mut step: S, done: D, limit: usize) -> Option<usize>
+    where
+        S: FnMut(&mut Self),
+        D: Fn(&Grid<T>) -> bool,
+    {
+        while self.generation < limit {
+            step(self);
+            if done(&self.grid) {
+                return Some(self.generation);
+            }
+        }
+        None
+    }
+
+    // applies the simultaneous rule until nothing changes anymore,
+    // returns the first generation identical to its predecessor
+    pub fn run_until_stable<F>(&mut self, mut rule: F, limit: usize) -> Option<usize>
+    where
+        T: PartialEq,
+        F: FnMut(&Grid<T>, (usize, usize), &T) -> T,
+    {
+        while self.generation < limit {
+            if !self.step(&mut rule) {
+                return Some(self.generation);
+            }
+        }
+        None
+    }
+
+    // applies the simultaneous rule until a previously seen state comes back, see
+    // `cycle::find_cycle_hashed`; returns the cycle in generations, none if it isn't found
+    // before the `limit` generation
+    pub fn run_until_cycle<F>(&mut self, mut rule: F, limit: usize) -> Option<Cycle>
+    where
+        T: Eq + Hash,
+        F: FnMut(&Grid<T>, (usize, usize), &T) -> T,
+    {
+        let first = self.generation;
+        let initial = self.grid.clone();
+        let (cycle, _) = find_cycle_hashed(
+            initial,
+            |_| {
+                self.step(&mut rule);
+                self.grid.clone()
+            },
+            limit.saturating_sub(first),
+        )?;
+
+        Some(Cycle {
+            start: first + cycle.start,
+            len: cycle.len,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blinker() -> Grid<bool> {
+        Grid::from_reader_callback(".....\n..#..\n..#..\n..#..\n.....".as_bytes(), |b| {
+            Ok::<_, ()>(b == b'#')
+        })
+        .unwrap()
+    }
+
+    fn life(g: &Grid<bool>, c: (usize, usize), &alive: &bool) -> bool {
+        let n = g.neighbours8(c).into_iter().filter(|&&b| b).count();
+        n == 3 || (alive && n == 2)
+    }
+
+    #[test]
+    fn step_is_simultaneous() {
+        let g = blinker().step(life);
+        let alive = g.as_slice().iter().filter(|&&b| b).count();
+        assert_eq!(alive, 3);
+        assert_eq!(
+            g.iter_row(2).cloned().collect::<Vec<_>>(),
+            vec![false, true, true, true, false]
+        );
+    }
+
+    #[test]
+    fn blinker_cycle() {
+        let mut a = Automaton::new(blinker());
+        assert_eq!(a.run_until_cycle(life, 1), None);
+        assert_eq!(
+            a.run_until_cycle(life, 10),
+            Some(Cycle { start: 1, len: 2 })
+        );
+    }
+
+    #[test]
+    fn cascade_propagates() {
+        let mut g =
+            Grid::from_reader_callback("988\n111".as_bytes(), |b| Ok::<_, ()>(b - b'0')).unwrap();
+        let flash = |c: &mut u8| {
+            *c += 1;
+            *c > 9
+        };
+        let triggered = g.cascade(Connectivity::Four, flash, flash);
+
+        assert_eq!(triggered, vec![(0, 0), (1, 0), (2, 0)]);
+        assert_eq!(g.as_slice(), &[10, 10, 10, 3, 3, 3]);
+
+        let mut a = Automaton::new(g);
+        let triggered = a.cascade(Connectivity::Four, flash, flash, |c| *c = 0);
+        assert_eq!(triggered, vec![(0, 0), (1, 0), (2, 0)]);
+        assert_eq!(a.grid().as_slice(), &[0, 0, 0, 5, 5, 5]);
+        assert_eq!(a.generation(), 1);
+    }
+}
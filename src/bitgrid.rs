@@ -0,0 +1,231 @@
+use crate::grid::{Coord, Grid};
+use std::fmt::{Display, Formatter};
+use std::ops::{BitAndAssign, BitOrAssign};
+
+// grid of booleans packed 64 cells per word, each row starting on a new word so that whole
+// rows can be combined word by word; bits past the width of a row are always zero
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    stride: usize,
+    pub w: usize,
+    pub h: usize,
+}
+
+#[allow(unused)]
+impl BitGrid {
+    pub fn new(w: usize, h: usize) -> Self {
+        let stride = w.div_ceil(64);
+        Self {
+            words: vec![0; stride * h],
+            stride,
+            w,
+            h,
+        }
+    }
+
+    pub fn from_grid<T>(g: &Grid<T>, f: impl Fn(&T) -> bool) -> Self {
+        let mut b = Self::new(g.w, g.h);
+        for (c, v) in g.enumerate_cells() {
+            if f(v) {
+                b.set(&c, true);
+            }
+        }
+        b
+    }
+
+    pub fn to_grid(&self) -> Grid<bool> {
+        let cells = (0..self.h)
+            .flat_map(|y| (0..self.w).map(move |x| (x, y)))
+            .map(|c| self.get(&c))
+            .collect();
+        Grid::from_cells(self.w, self.h, cells)
+    }
+
+    pub fn contains_coord(&self, c: &impl Coord) -> bool {
+        c.x() < self.w && c.y() < self.h
+    }
+
+    fn index(&self, c: &impl Coord) -> (usize, u64) {
+        (c.y() * self.stride + c.x() / 64, 1 << (c.x() % 64))
+    }
+
+    // false outside of the grid
+    pub fn get(&self, c: &impl Coord) -> bool {
+        if !self.contains_coord(c) {
+            return false;
+        }
+        let (i, bit) = self.index(c);
+        self.words[i] & bit != 0
+    }
+
+    pub fn set(&mut self, c: &impl Coord, v: bool) {
+        assert!(self.contains_coord(c), "coordinates out of the grid");
+        let (i, bit) = self.index(c);
+        if v {
+            self.words[i] |= bit;
+        } else {
+            self.words[i] &= !bit;
+        }
+    }
+
+    // sets the cell, returning whether it was already set
+    pub fn replace(&mut self, c: &impl Coord) -> bool {
+        let old = self.get(c);
+        self.set(c, true);
+        old
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn row_words(&self, y: usize) -> &[u64] {
+        &self.words[y * self.stride..(y + 1) * self.stride]
+    }
+
+    fn row_words_mut(&mut self, y: usize) -> &mut [u64] {
+        &mut self.words[y * self.stride..(y + 1) * self.stride]
+    }
+
+    // ORs row `src_y` of another grid with the same width into row `y`
+    pub fn or_row(&mut self, y: usize, other: &BitGrid, src_y: usize) {
+        assert_eq!(self.w, other.w, "grid widths differ");
+        for (a, b) in self.row_words_mut(y).iter_mut().zip(other.row_words(src_y)) {
+            *a |= b;
+        }
+    }
+
+    // ANDs row `src_y` of another grid with the same width into row `y`
+    pub fn and_row(&mut self, y: usize, other: &BitGrid, src_y: usize) {
+        assert_eq!(self.w, other.w, "grid widths differ");
+        for (a, b) in self.row_words_mut(y).iter_mut().zip(other.row_words(src_y)) {
+            *a &= b;
+        }
+    }
+
+    // folds the part below row `at` up over the part above it, the row itself disappearing
+    pub fn fold_up(&self, at: usize) -> BitGrid {
+        let mut folded = BitGrid::new(self.w, at.min(self.h));
+        for y in 0..folded.h {
+            folded.or_row(y, self, y);
+            if let Some(src_y) = (2 * at).checked_sub(y).filter(|&y| y < self.h) {
+                folded.or_row(y, self, src_y);
+            }
+        }
+        folded
+    }
+
+    // folds the part right of column `at` over the part left of it, the column itself disappearing
+    pub fn fold_left(&self, at: usize) -> BitGrid {
+        let mut folded = BitGrid::new(at.min(self.w), self.h);
+        for (x, y) in self.iter_ones() {
+            let x = match x {
+                x if x > at => (2 * at).checked_sub(x),
+                x => Some(x),
+            };
+            if let Some(x) = x.filter(|&x| x < folded.w) {
+                folded.set(&(x, y), true);
+            }
+        }
+        folded
+    }
+
+    // coordinates of the set cells, row by row
+    pub fn iter_ones(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, &word)| {
+            let (y, x0) = (i / self.stride, i % self.stride * 64);
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let x = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some((x0 + x, y))
+            })
+        })
+    }
+}
+
+impl BitOrAssign<&BitGrid> for BitGrid {
+    fn bitor_assign(&mut self, other: &BitGrid) {
+        assert_eq!((self.w, self.h), (other.w, other.h), "grid sizes differ");
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a |= b;
+        }
+    }
+}
+
+impl BitAndAssign<&BitGrid> for BitGrid {
+    fn bitand_assign(&mut self, other: &BitGrid) {
+        assert_eq!((self.w, self.h), (other.w, other.h), "grid sizes differ");
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a &= b;
+        }
+    }
+}
+
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.h {
+            for x in 0..self.w {
+                write!(f, "{}", if self.get(&(x, y)) { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_points(w: usize, h: usize, points: &[(usize, usize)]) -> BitGrid {
+        let mut g = BitGrid::new(w, h);
+        for p in points {
+            g.set(p, true);
+        }
+        g
+    }
+
+    #[test]
+    fn set_and_count() {
+        let mut g = from_points(130, 2, &[(0, 0), (64, 0), (129, 1)]);
+        assert_eq!(g.count_ones(), 3);
+        assert!(g.get(&(129, 1)));
+        assert!(!g.get(&(130, 1)));
+        assert!(!g.replace(&(1, 1)));
+        assert!(g.replace(&(1, 1)));
+        assert_eq!(
+            g.iter_ones().collect::<Vec<_>>(),
+            vec![(0, 0), (64, 0), (1, 1), (129, 1)]
+        );
+    }
+
+    #[test]
+    fn folds() {
+        let g = from_points(5, 5, &[(0, 0), (3, 0), (4, 4), (1, 3)]);
+        let up = g.fold_up(2);
+        assert_eq!(up.to_string(), "#..##\n.#...\n");
+        let left = up.fold_left(2);
+        assert_eq!(left.to_string(), "##\n.#\n");
+        assert_eq!(left.count_ones(), 3);
+    }
+
+    #[test]
+    fn combine() {
+        let mut a = from_points(3, 2, &[(0, 0), (1, 1)]);
+        let b = from_points(3, 2, &[(0, 0), (2, 1)]);
+        let mut c = a.clone();
+        c &= &b;
+        a |= &b;
+        assert_eq!(a.count_ones(), 3);
+        assert_eq!(c.to_string(), "#..\n...\n");
+    }
+}
@@ -0,0 +1,192 @@
+use std::str::FromStr;
+
+// number read from a binary string, along with its number of digits
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Binary {
+    pub value: u64,
+    pub width: usize,
+}
+
+impl FromStr for Binary {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || s.len() > 64 {
+            return Err("invalid binary width");
+        }
+        let value = s.bytes().try_fold(0, |v, b| match b {
+            b'0' => Ok(v << 1),
+            b'1' => Ok(v << 1 | 1),
+            _ => Err("invalid binary digit"),
+        })?;
+
+        Ok(Self {
+            value,
+            width: s.len(),
+        })
+    }
+}
+
+// the `width` lowest bits set
+pub fn mask(width: usize) -> u64 {
+    if width >= 64 {
+        u64::MAX
+    } else {
+        (1 << width) - 1
+    }
+}
+
+// number of values having each bit set, index 0 being the least significant bit
+pub fn count_ones(values: &[u64], width: usize) -> Vec<usize> {
+    (0..width)
+        .map(|bit| values.iter().filter(|&&v| v >> bit & 1 == 1).count())
+        .collect()
+}
+
+// bits set in more than half of the values, ties counting as zeroes
+pub fn most_common_mask(values: &[u64], width: usize) -> u64 {
+    count_ones(values, width)
+        .iter()
+        .enumerate()
+        .filter(|&(_, &n)| n * 2 > values.len())
+        .fold(0, |m, (bit, _)| m | 1 << bit)
+}
+
+// bits set in at most half of the values, ties counting as ones
+pub fn least_common_mask(values: &[u64], width: usize) -> u64 {
+    !most_common_mask(values, width) & mask(width)
+}
+
+// reads numbers of arbitrary bit widths from a stream of bits, most significant bit first
+#[derive(Clone, Debug)]
+pub struct BitReader {
+    bytes: Vec<u8>,
+    len: usize,
+    pos: usize,
+}
+
+#[allow(unused)]
+impl BitReader {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self {
+            len: bytes.len() * 8,
+            bytes,
+            pos: 0,
+        }
+    }
+
+    pub fn from_hex(s: &str) -> Result<Self, &'static str> {
+        let nibbles = s
+            .trim()
+            .chars()
+            .map(|c| c.to_digit(16).ok_or("invalid hex digit"))
+            .collect::<Result<Vec<_>, _>>()?;
+        let bytes = nibbles
+            .chunks(2)
+            .map(|n| (n[0] << 4 | n.get(1).copied().unwrap_or(0)) as u8)
+            .collect();
+
+        Ok(Self {
+            len: nibbles.len() * 4,
+            bytes,
+            pos: 0,
+        })
+    }
+
+    pub fn from_bits(s: &str) -> Result<Self, &'static str> {
+        let s = s.trim();
+        let mut bytes = vec![0; s.len().div_ceil(8)];
+        for (i, c) in s.bytes().enumerate() {
+            match c {
+                b'0' => {}
+                b'1' => bytes[i / 8] |= 0x80 >> (i % 8),
+                _ => return Err("invalid binary digit"),
+            }
+        }
+
+        Ok(Self {
+            bytes,
+            len: s.len(),
+            pos: 0,
+        })
+    }
+
+    // number of bits read so far
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.len - self.pos
+    }
+
+    pub fn read_bit(&mut self) -> Option<bool> {
+        self.read(1).map(|b| b == 1)
+    }
+
+    // next `n` bits as a number, none if there aren't enough bits left
+    pub fn read(&mut self, n: usize) -> Option<u64> {
+        if n > 64 || n > self.remaining() {
+            return None;
+        }
+
+        let mut v = 0;
+        for _ in 0..n {
+            let bit = self.bytes[self.pos / 8] >> (7 - self.pos % 8) & 1;
+            v = v << 1 | bit as u64;
+            self.pos += 1;
+        }
+        Some(v)
+    }
+
+    pub fn skip(&mut self, n: usize) -> Option<()> {
+        if n > self.remaining() {
+            return None;
+        }
+        self.pos += n;
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_numbers() {
+        let b = "10110".parse::<Binary>().unwrap();
+        assert_eq!(
+            b,
+            Binary {
+                value: 22,
+                width: 5
+            }
+        );
+        assert!("102".parse::<Binary>().is_err());
+
+        let values = [0b00100, 0b11110, 0b10110, 0b10111, 0b10101];
+        assert_eq!(count_ones(&values, 5), vec![2, 3, 5, 1, 4]);
+        assert_eq!(most_common_mask(&values, 5), 0b10110);
+        assert_eq!(least_common_mask(&values, 5), 0b01001);
+
+        let tied = [0b10, 0b11];
+        assert_eq!(most_common_mask(&tied, 2), 0b10);
+        assert_eq!(least_common_mask(&tied, 2), 0b01);
+    }
+
+    #[test]
+    fn bit_reader() {
+        let mut r = BitReader::from_hex("D2FE28").unwrap();
+        assert_eq!(r.read(3), Some(6));
+        assert_eq!(r.read(3), Some(4));
+        assert_eq!(r.read(5), Some(0b10111));
+        assert_eq!(r.position(), 11);
+        assert_eq!(r.remaining(), 13);
+        assert_eq!(r.read(14), None);
+
+        let mut r = BitReader::from_bits("1011").unwrap();
+        assert_eq!(r.read_bit(), Some(true));
+        assert_eq!(r.read(3), Some(0b011));
+        assert_eq!(r.read_bit(), None);
+    }
+}
@@ -0,0 +1,115 @@
+use crate::grid::{Connectivity, Edge, Grid, Rect};
+use std::collections::VecDeque;
+
+// connected regions of a grid, as found by `Grid::components`
+#[allow(unused)]
+#[derive(Clone, Debug)]
+pub struct Components {
+    // label of the component each cell belongs to, if any
+    pub labels: Grid<Option<usize>>,
+    // number of cells in each component, indexed by label
+    pub sizes: Vec<usize>,
+    // bounding box of each component, indexed by label
+    pub bounds: Vec<Rect>,
+}
+
+#[allow(unused)]
+impl Components {
+    pub fn len(&self) -> usize {
+        self.sizes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sizes.is_empty()
+    }
+
+    pub fn label(&self, c: (usize, usize)) -> Option<usize> {
+        self.labels.get(c).copied().flatten()
+    }
+}
+
+#[allow(unused)]
+impl<T> Grid<T> {
+    // labels all connected regions of cells matching the predicate,
+    // each cell is visited only once
+    pub fn components<P>(&self, predicate: P, connectivity: Connectivity) -> Components
+    where
+        P: Fn(&T) -> bool,
+    {
+        let mut labels = Grid::new_with(self.w, self.h, None);
+        let mut sizes = vec![];
+        let mut bounds = vec![];
+        let mut queue = VecDeque::new();
+
+        for y in 0..self.h {
+            for x in 0..self.w {
+                if labels.get((x, y)).copied().flatten().is_some()
+                    || !self.get((x, y)).is_some_and(&predicate)
+                {
+                    continue;
+                }
+
+                // new component, flood it
+                let label = sizes.len();
+                let mut size = 0;
+                let mut rect = Rect::default();
+
+                if let Some(l) = labels.get_mut((x, y)) {
+                    *l = Some(label);
+                }
+                queue.push_back((x, y));
+
+                while let Some(c) = queue.pop_front() {
+                    size += 1;
+                    rect.extend(&c);
+
+                    for n in self.neighbours_coords_with(c, connectivity, Edge::Skip) {
+                        if !self.get(n).is_some_and(&predicate) {
+                            continue;
+                        }
+                        if let Some(l) = labels.get_mut(n) {
+                            if l.is_none() {
+                                *l = Some(label);
+                                queue.push_back(n);
+                            }
+                        }
+                    }
+                }
+
+                sizes.push(size);
+                bounds.push(rect);
+            }
+        }
+
+        Components {
+            labels,
+            sizes,
+            bounds,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<u8> {
+        Grid::from_reader_callback("##..#\n#...#\n.#...".as_bytes(), Ok::<_, ()>).unwrap()
+    }
+
+    #[test]
+    fn components4() {
+        let c = grid().components(|&b| b == b'#', Connectivity::Four);
+        assert_eq!(c.sizes, vec![3, 2, 1]);
+        assert_eq!(c.bounds[1], Rect::new(4, 0, 1, 2));
+        assert_eq!(c.label((1, 2)), Some(2));
+        assert_eq!(c.label((1, 1)), None);
+    }
+
+    #[test]
+    fn components8() {
+        let c = grid().components(|&b| b == b'#', Connectivity::Eight);
+        assert_eq!(c.sizes, vec![4, 2]);
+        assert_eq!(c.bounds[0], Rect::new(0, 0, 2, 3));
+    }
+}
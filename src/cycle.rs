@@ -0,0 +1,160 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// states repeat from step `start` on, every `len` steps
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cycle {
+    pub start: usize,
+    pub len: usize,
+}
+
+impl Cycle {
+    // earliest step having the same state as step `n`
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.len
+        }
+    }
+}
+
+// Brent's algorithm, in constant memory but computing each step several times;
+// the states must repeat eventually, otherwise this never returns
+#[allow(unused)]
+pub fn find_cycle<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    // find the cycle length by moving the hare ahead of a tortoise teleported to it at each
+    // power of two
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = step(&hare);
+        len += 1;
+    }
+
+    // then move both one cycle length apart until they meet at the start of the cycle
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..len {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, len }
+}
+
+// remembers every state until one comes back, computing each step only once;
+// returns the cycle and all the states up to its end, none if the cycle isn't closed within
+// `limit` steps
+pub fn find_cycle_hashed<S, F>(initial: S, mut step: F, limit: usize) -> Option<(Cycle, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut states = vec![initial];
+    loop {
+        let last = states.last().expect("at least one state");
+        match seen.entry(last.clone()) {
+            Entry::Occupied(e) => {
+                let start = *e.get();
+                states.pop();
+                return Some((
+                    Cycle {
+                        start,
+                        len: states.len() - start,
+                    },
+                    states,
+                ));
+            }
+            Entry::Vacant(e) => {
+                e.insert(states.len() - 1);
+            }
+        }
+        if states.len() > limit {
+            return None;
+        }
+        let next = step(last);
+        states.push(next);
+    }
+}
+
+// state after `n` steps, skipping whole cycles once the states start repeating
+#[allow(unused)]
+pub fn nth_state<S, F>(initial: S, mut step: F, n: usize) -> S
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut states = vec![initial];
+    for i in 0..n {
+        let last = &states[i];
+        if let Some(&start) = seen.get(last) {
+            let cycle = Cycle {
+                start,
+                len: i - start,
+            };
+            return states.swap_remove(cycle.reduce(n));
+        }
+        seen.insert(last.clone(), i);
+        let next = step(last);
+        states.push(next);
+    }
+    states.swap_remove(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 3 -> 10 -> 5 -> 16 -> 8 -> 4 -> 2 -> 1 -> 4 -> ...
+    fn collatz(&n: &u64) -> u64 {
+        if n % 2 == 0 {
+            n / 2
+        } else {
+            3 * n + 1
+        }
+    }
+
+    #[test]
+    fn cycles() {
+        let expected = Cycle { start: 5, len: 3 };
+        assert_eq!(find_cycle(3, collatz), expected);
+
+        let (cycle, states) = find_cycle_hashed(3, collatz, 8).unwrap();
+        assert_eq!(cycle, expected);
+        assert_eq!(states, vec![3, 10, 5, 16, 8, 4, 2, 1]);
+        assert_eq!(find_cycle_hashed(3, collatz, 7), None);
+
+        assert_eq!(
+            find_cycle(0u8, |&n| (n + 1) % 7),
+            Cycle { start: 0, len: 7 }
+        );
+    }
+
+    #[test]
+    fn fast_forward() {
+        assert_eq!(nth_state(3, collatz, 2), 5);
+        assert_eq!(nth_state(3, collatz, 7), 1);
+        assert_eq!(nth_state(3, collatz, 1_000_000_000), 1);
+        assert_eq!(Cycle { start: 5, len: 3 }.reduce(1_000_000_000), 7);
+    }
+}
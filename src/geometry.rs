@@ -0,0 +1,232 @@
+use crate::point::{Point2, Vec2};
+use crate::solver::scan;
+use std::collections::HashMap;
+use std::error::Error;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Segment {
+    pub from: Point2,
+    pub to: Point2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Intersection {
+    // segments cross at a single point, which may lie between lattice points
+    Point(f64, f64),
+    // segments are collinear and share the part between these two points
+    Overlap(Point2, Point2),
+}
+
+fn cross(a: Vec2, b: Vec2) -> i64 {
+    a.x * b.y - a.y * b.x
+}
+
+fn dot(a: Vec2, b: Vec2) -> i64 {
+    a.x * b.x + a.y * b.y
+}
+
+#[allow(unused)]
+impl Segment {
+    pub fn new(from: Point2, to: Point2) -> Self {
+        Self { from, to }
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        self.from.y == self.to.y
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        self.from.x == self.to.x
+    }
+
+    pub fn is_axis_aligned(&self) -> bool {
+        self.is_horizontal() || self.is_vertical()
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        let d = self.to - self.from;
+        d.x.abs() == d.y.abs()
+    }
+
+    // all lattice points of the segment, both ends included, following Bresenham's algorithm
+    // (which is exact for horizontal, vertical and 45° segments)
+    pub fn points(&self) -> Points {
+        let d = self.to - self.from;
+        Points {
+            pos: Some(self.from),
+            to: self.to,
+            dx: d.x.abs(),
+            dy: -d.y.abs(),
+            step: d.signum(),
+            err: d.x.abs() - d.y.abs(),
+        }
+    }
+
+    pub fn contains(&self, p: Point2) -> bool {
+        let d = self.to - self.from;
+        let ap = p - self.from;
+        cross(ap, d) == 0 && dot(ap, d) >= 0 && dot(ap, d) <= dot(d, d)
+    }
+
+    pub fn intersection(&self, other: &Segment) -> Option<Intersection> {
+        let d1 = self.to - self.from;
+        let d2 = other.to - other.from;
+        let ac = other.from - self.from;
+
+        // single points are handled separately since they have no direction
+        if d1 == Vec2::ZERO || d2 == Vec2::ZERO {
+            let (p, s) = if d1 == Vec2::ZERO {
+                (self.from, other)
+            } else {
+                (other.from, self)
+            };
+            return s.contains(p).then_some(Intersection::Overlap(p, p));
+        }
+
+        let denom = cross(d1, d2);
+        if denom != 0 {
+            // solve from + t * d1 == other.from + u * d2
+            let t = cross(ac, d2);
+            let u = cross(ac, d1);
+            let in_range = |n: i64| {
+                if denom > 0 {
+                    (0..=denom).contains(&n)
+                } else {
+                    (denom..=0).contains(&n)
+                }
+            };
+            if !in_range(t) || !in_range(u) {
+                return None;
+            }
+            let t = t as f64 / denom as f64;
+            return Some(Intersection::Point(
+                self.from.x as f64 + t * d1.x as f64,
+                self.from.y as f64 + t * d1.y as f64,
+            ));
+        }
+
+        // parallel, and not on the same line
+        if cross(ac, d1) != 0 {
+            return None;
+        }
+
+        // collinear: order the ends of both segments along the line
+        let along = |p: Point2| dot(p - self.from, d1);
+        let (a0, a1) = (self.from, self.to);
+        let (b0, b1) = if along(other.from) <= along(other.to) {
+            (other.from, other.to)
+        } else {
+            (other.to, other.from)
+        };
+        let start = if along(b0) > along(a0) { b0 } else { a0 };
+        let end = if along(b1) < along(a1) { b1 } else { a1 };
+
+        (along(start) <= along(end)).then_some(Intersection::Overlap(start, end))
+    }
+}
+
+impl FromStr for Segment {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x0, y0, x1, y1) = scan!("{},{} -> {},{}", s => i64, i64, i64, i64)?;
+        Ok(Self::new(Point2::new(x0, y0), Point2::new(x1, y1)))
+    }
+}
+
+pub struct Points {
+    pos: Option<Point2>,
+    to: Point2,
+    dx: i64,
+    dy: i64,
+    step: Vec2,
+    err: i64,
+}
+
+impl Iterator for Points {
+    type Item = Point2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let p = self.pos?;
+        if p == self.to {
+            self.pos = None;
+            return Some(p);
+        }
+
+        let mut next = p;
+        let e2 = 2 * self.err;
+        if e2 >= self.dy {
+            self.err += self.dy;
+            next.x += self.step.x;
+        }
+        if e2 <= self.dx {
+            self.err += self.dx;
+            next.y += self.step.y;
+        }
+        self.pos = Some(next);
+
+        Some(p)
+    }
+}
+
+// number of segments covering each lattice point
+pub fn count_overlaps<'a, I>(segments: I) -> HashMap<Point2, usize>
+where
+    I: IntoIterator<Item = &'a Segment>,
+{
+    let mut counts = HashMap::new();
+    for s in segments {
+        for p in s.points() {
+            *counts.entry(p).or_default() += 1;
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seg(x0: i64, y0: i64, x1: i64, y1: i64) -> Segment {
+        Segment::new(Point2::new(x0, y0), Point2::new(x1, y1))
+    }
+
+    #[test]
+    fn rasterize() {
+        let pts = |s: Segment| s.points().map(|p| (p.x, p.y)).collect::<Vec<_>>();
+        assert_eq!(pts(seg(1, 1, 1, 3)), vec![(1, 1), (1, 2), (1, 3)]);
+        assert_eq!(pts(seg(9, 7, 7, 9)), vec![(9, 7), (8, 8), (7, 9)]);
+        assert_eq!(
+            pts(seg(0, 0, 4, 2)),
+            vec![(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)]
+        );
+        assert_eq!(pts(seg(2, 2, 2, 2)), vec![(2, 2)]);
+    }
+
+    #[test]
+    fn intersections() {
+        assert_eq!(
+            seg(0, 0, 4, 4).intersection(&seg(0, 4, 4, 0)),
+            Some(Intersection::Point(2.0, 2.0))
+        );
+        assert_eq!(
+            seg(0, 0, 1, 1).intersection(&seg(0, 1, 1, 0)),
+            Some(Intersection::Point(0.5, 0.5))
+        );
+        assert_eq!(seg(0, 0, 4, 0).intersection(&seg(0, 1, 4, 1)), None);
+        assert_eq!(seg(0, 0, 1, 0).intersection(&seg(3, -1, 3, 1)), None);
+        assert_eq!(
+            seg(0, 0, 6, 0).intersection(&seg(8, 0, 3, 0)),
+            Some(Intersection::Overlap(Point2::new(3, 0), Point2::new(6, 0)))
+        );
+        assert_eq!(seg(0, 0, 2, 2).intersection(&seg(3, 3, 5, 5)), None);
+    }
+
+    #[test]
+    fn overlaps() {
+        let segments = [seg(0, 0, 4, 0), seg(2, 0, 2, 3), seg(0, 0, 2, 2)];
+        let counts = count_overlaps(&segments);
+        assert_eq!(counts.values().filter(|&&n| n >= 2).count(), 3);
+    }
+}
@@ -0,0 +1,294 @@
+use crate::image::Rgb;
+use crate::replay::Recorder;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::Duration;
+
+const MAX_CODES: u16 = 4096;
+
+#[allow(unused)]
+impl<T> Recorder<T>
+where
+    T: Clone,
+{
+    // writes all recorded frames as a looping animated GIF,
+    // `cell` maps each cell to an index in the palette (at most 256 colors),
+    // and every frame is displayed for `delay` (with a resolution of 10ms)
+    pub fn write_gif<W, F>(
+        &self,
+        mut w: W,
+        palette: &[Rgb],
+        scale: usize,
+        delay: Duration,
+        cell: F,
+    ) -> io::Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> u8,
+    {
+        if palette.is_empty() || palette.len() > 256 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "palette must have between 1 and 256 colors",
+            ));
+        }
+
+        // frames may have different sizes, the screen fits the largest one
+        let width = self.frames().iter().map(|f| f.grid.w).max().unwrap_or(0) * scale;
+        let height = self.frames().iter().map(|f| f.grid.h).max().unwrap_or(0) * scale;
+        let width = u16::try_from(width).map_err(|_| too_large())?;
+        let height = u16::try_from(height).map_err(|_| too_large())?;
+
+        // the color table size must be a power of two, 2^(bits)
+        let bits = (1..=8).find(|&b| 1 << b >= palette.len()).unwrap_or(8);
+
+        w.write_all(b"GIF89a")?;
+        w.write_all(&width.to_le_bytes())?;
+        w.write_all(&height.to_le_bytes())?;
+        // global color table, 8 bits per channel, table size
+        w.write_all(&[0xf0 | (bits as u8 - 1), 0, 0])?;
+        for i in 0..1 << bits {
+            let Rgb(r, g, b) = palette.get(i).copied().unwrap_or_default();
+            w.write_all(&[r, g, b])?;
+        }
+
+        // loop forever
+        w.write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00")?;
+
+        let delay = (delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+        for frame in self.frames() {
+            let fw = (frame.grid.w * scale) as u16;
+            let fh = (frame.grid.h * scale) as u16;
+
+            // graphic control: restore to background after display, delay
+            w.write_all(&[0x21, 0xf9, 0x04, 0x08])?;
+            w.write_all(&delay.to_le_bytes())?;
+            w.write_all(&[0, 0])?;
+
+            // image descriptor at the top left corner, no local color table
+            w.write_all(&[0x2c, 0, 0, 0, 0])?;
+            w.write_all(&fw.to_le_bytes())?;
+            w.write_all(&fh.to_le_bytes())?;
+            w.write_all(&[0])?;
+
+            let pixels = frame
+                .grid
+                .scaled_rows(scale, &cell)
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>();
+            let min_code_size = bits.max(2) as u8;
+            w.write_all(&[min_code_size])?;
+            for block in lzw_encode(&pixels, min_code_size).chunks(255) {
+                w.write_all(&[block.len() as u8])?;
+                w.write_all(block)?;
+            }
+            w.write_all(&[0])?;
+        }
+
+        w.write_all(&[0x3b])
+    }
+
+    pub fn save_gif<P, F>(
+        &self,
+        p: P,
+        palette: &[Rgb],
+        scale: usize,
+        delay: Duration,
+        cell: F,
+    ) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+        F: Fn(&T) -> u8,
+    {
+        let w = BufWriter::new(File::create(p)?);
+        self.write_gif(w, palette, scale, delay, cell)
+    }
+}
+
+fn too_large() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, "image too large for GIF")
+}
+
+// packs variable-width codes, least significant bits first
+struct BitWriter {
+    out: Vec<u8>,
+    acc: u32,
+    n: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u32) {
+        self.acc |= (code as u32) << self.n;
+        self.n += size;
+        while self.n >= 8 {
+            self.out.push(self.acc as u8);
+            self.acc >>= 8;
+            self.n -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.n > 0 {
+            self.out.push(self.acc as u8);
+        }
+        self.out
+    }
+}
+
+// GIF flavoured LZW, with variable code sizes up to 12 bits
+fn lzw_encode(data: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let eoi = clear + 1;
+    let initial_size = min_code_size as u32 + 1;
+
+    let mut w = BitWriter {
+        out: vec![],
+        acc: 0,
+        n: 0,
+    };
+    let mut dict = HashMap::new();
+    let mut next = eoi + 1;
+    let mut size = initial_size;
+
+    w.write(clear, size);
+
+    let mut data = data.iter();
+    let mut current = match data.next() {
+        Some(&b) => b as u16,
+        None => {
+            w.write(eoi, size);
+            return w.finish();
+        }
+    };
+
+    for &b in data {
+        if let Some(&code) = dict.get(&(current, b)) {
+            current = code;
+            continue;
+        }
+
+        w.write(current, size);
+        if next < MAX_CODES {
+            dict.insert((current, b), next);
+            next += 1;
+            // the decoder lags one code behind, so the size grows once the
+            // newly added code doesn't fit anymore
+            if next > 1 << size && size < 12 {
+                size += 1;
+            }
+        } else {
+            w.write(clear, size);
+            dict.clear();
+            next = eoi + 1;
+            size = initial_size;
+        }
+        current = b as u16;
+    }
+
+    w.write(current, size);
+    w.write(eoi, size);
+    w.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    fn lzw_decode(data: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let eoi = clear + 1;
+        let mut table: Vec<Vec<u8>> = vec![];
+        let mut size = min_code_size as u32 + 1;
+        let mut prev: Option<usize> = None;
+        let mut out = vec![];
+
+        let (mut acc, mut n) = (0u32, 0u32);
+        let mut bytes = data.iter();
+        loop {
+            while n < size {
+                match bytes.next() {
+                    Some(&b) => {
+                        acc |= (b as u32) << n;
+                        n += 8;
+                    }
+                    None => return out,
+                }
+            }
+            let code = (acc & ((1 << size) - 1)) as usize;
+            acc >>= size;
+            n -= size;
+
+            if code == clear {
+                table = (0..clear).map(|i| vec![i as u8]).collect();
+                table.push(vec![]);
+                table.push(vec![]);
+                size = min_code_size as u32 + 1;
+                prev = None;
+                continue;
+            }
+            if code == eoi {
+                return out;
+            }
+
+            let entry = match (table.get(code), prev) {
+                (Some(e), _) if code != table.len() => e.clone(),
+                (_, Some(p)) => {
+                    let mut e = table[p].clone();
+                    e.push(table[p][0]);
+                    e
+                }
+                _ => panic!("invalid code"),
+            };
+            if let Some(p) = prev {
+                if table.len() < MAX_CODES as usize {
+                    let mut e = table[p].clone();
+                    e.push(entry[0]);
+                    table.push(e);
+                    if table.len() == 1 << size && size < 12 {
+                        size += 1;
+                    }
+                }
+            }
+            out.extend_from_slice(&entry);
+            prev = Some(code);
+        }
+    }
+
+    #[test]
+    fn lzw_roundtrip() {
+        let data = (0..20000u32)
+            .map(|i| ((i * i / 7 + i / 13) % 5) as u8)
+            .collect::<Vec<_>>();
+        assert_eq!(lzw_decode(&lzw_encode(&data, 3), 3), data);
+
+        let data = vec![1u8; 50000];
+        assert_eq!(lzw_decode(&lzw_encode(&data, 2), 2), data);
+    }
+
+    #[test]
+    fn gif_structure() {
+        let mut rec = Recorder::new();
+        rec.record(&Grid::new_with(2, 3, 0u8));
+        rec.record(&Grid::new_with(4, 1, 1u8));
+
+        let mut out = vec![];
+        rec.write_gif(
+            &mut out,
+            &[Rgb::BLACK, Rgb::WHITE],
+            2,
+            Duration::from_millis(500),
+            |&v| v,
+        )
+        .unwrap();
+
+        assert_eq!(&out[..6], b"GIF89a");
+        // screen size fits the largest frame
+        assert_eq!(&out[6..10], &[8, 0, 6, 0]);
+        assert_eq!(out.last(), Some(&0x3b));
+    }
+}
@@ -1,11 +1,13 @@
+use crate::point::Point2;
 use std::collections::HashMap;
 use std::convert::TryFrom;
-use std::fmt::{Display, Error, Formatter};
+use std::fmt::{Debug, Display, Error, Formatter};
 use std::io::{BufRead, BufReader, Read};
-use std::iter::{repeat, FromIterator};
+use std::iter::FromIterator;
+use std::slice::{Chunks, ChunksMut, Iter, IterMut};
 use std::str::FromStr;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Grid<T> {
     cells: Vec<T>,
     pub w: usize,
@@ -26,64 +28,130 @@ impl<T> Grid<T> {
         T: Clone,
     {
         Self {
-            cells: Vec::from_iter(repeat(val).take(w * h)),
+            cells: Vec::from_iter(std::iter::repeat_n(val, w * h)),
             w,
             h,
         }
     }
 
-    pub fn from_reader_callback<R, F, E>(r: R, f: F) -> Result<Self, E>
+    pub fn from_cells(w: usize, h: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), w * h, "cell count doesn't match grid size");
+        Self { cells, w, h }
+    }
+
+    pub fn from_reader_callback<R, F, E>(r: R, f: F) -> Result<Self, GridError<E>>
     where
         R: Read,
         F: FnMut(u8) -> Result<T, E> + Copy,
     {
-        let cells = BufReader::new(r)
-            .lines()
-            .filter_map(|l| l.ok())
-            .map(|l| l.bytes().map(f).collect::<Result<Vec<_>, _>>())
-            .collect::<Result<Vec<_>, _>>()?;
-        let h = cells.len();
-        let w = cells.first().map_or(0, |c| c.len());
-
-        Ok(Self {
-            cells: cells.into_iter().flatten().collect(),
-            w,
-            h,
-        })
+        Self::from_rows(Self::parse_rows_callback(r, f)?, None)
     }
 
-    pub fn from_reader<R: Read>(r: R) -> Result<Self, T::Error>
+    // same as `from_reader_callback`, but rows shorter than the longest one
+    // are padded with default cells instead of being rejected
+    pub fn from_reader_callback_padded<R, F, E>(r: R, f: F) -> Result<Self, GridError<E>>
+    where
+        T: Default,
+        R: Read,
+        F: FnMut(u8) -> Result<T, E> + Copy,
+    {
+        Self::from_rows(Self::parse_rows_callback(r, f)?, Some(T::default))
+    }
+
+    pub fn from_reader<R: Read>(r: R) -> Result<Self, GridError<T::Error>>
     where
         T: TryFrom<u8>,
     {
         Self::from_reader_callback(r, T::try_from)
     }
 
-    pub fn from_split_whitespace_reader<R>(r: R) -> Result<Self, T::Err>
+    pub fn from_split_whitespace_reader<R>(r: R) -> Result<Self, GridError<T::Err>>
+    where
+        T: FromStr,
+        R: Read,
+    {
+        Self::from_rows(Self::parse_rows_split_whitespace(r)?, None)
+    }
+
+    pub fn from_split_whitespace_reader_padded<R>(r: R) -> Result<Self, GridError<T::Err>>
+    where
+        T: Default + FromStr,
+        R: Read,
+    {
+        Self::from_rows(Self::parse_rows_split_whitespace(r)?, Some(T::default))
+    }
+
+    fn parse_rows_callback<R, F, E>(r: R, f: F) -> Result<Vec<Vec<T>>, GridError<E>>
+    where
+        R: Read,
+        F: FnMut(u8) -> Result<T, E> + Copy,
+    {
+        BufReader::new(r)
+            .lines()
+            .map_while(Result::ok)
+            .enumerate()
+            .map(|(row, l)| {
+                l.bytes()
+                    .map(f)
+                    .enumerate()
+                    .map(|(col, c)| c.map_err(|err| GridError::Cell { row, col, err }))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect()
+    }
+
+    fn parse_rows_split_whitespace<R>(r: R) -> Result<Vec<Vec<T>>, GridError<T::Err>>
     where
         T: FromStr,
         R: Read,
     {
-        let cells = BufReader::new(r)
+        BufReader::new(r)
             .lines()
-            .filter_map(|l| l.ok())
-            .map(|l| {
+            .map_while(Result::ok)
+            .enumerate()
+            .map(|(row, l)| {
                 l.split_whitespace()
                     .map(T::from_str)
+                    .enumerate()
+                    .map(|(col, c)| c.map_err(|err| GridError::Cell { row, col, err }))
                     .collect::<Result<Vec<_>, _>>()
             })
-            .collect::<Result<Vec<_>, _>>()?;
-        let h = cells.len();
-        let w = cells.first().map_or(0, |c| c.len());
+            .collect()
+    }
 
-        Ok(Self {
-            cells: cells.into_iter().flatten().collect(),
-            w,
-            h,
-        })
+    // builds a grid out of rows which must all have the same width,
+    // unless a padding cell is given to complete the shorter ones
+    pub(crate) fn from_rows<E>(
+        rows: Vec<Vec<T>>,
+        pad: Option<fn() -> T>,
+    ) -> Result<Self, GridError<E>> {
+        let h = rows.len();
+        let w = match pad {
+            Some(_) => rows.iter().map(|r| r.len()).max().unwrap_or(0),
+            None => rows.first().map_or(0, |r| r.len()),
+        };
+
+        let mut cells = Vec::with_capacity(w * h);
+        for (row, mut r) in rows.into_iter().enumerate() {
+            if r.len() != w {
+                match pad {
+                    Some(p) => r.resize_with(w, p),
+                    None => {
+                        return Err(GridError::Ragged {
+                            row,
+                            width: r.len(),
+                            expected: w,
+                        })
+                    }
+                }
+            }
+            cells.extend(r);
+        }
+
+        Ok(Self { cells, w, h })
     }
 
-    pub fn from_map(points: HashMap<Point, T>) -> Self
+    pub fn from_map(points: HashMap<Point2, T>) -> Self
     where
         T: Clone + Default,
     {
@@ -110,7 +178,7 @@ impl<T> Grid<T> {
         let x_offset = -min_x;
         let y_offset = -min_y;
 
-        let mut grid = Self::new(w as usize, h as usize);
+        let mut grid = Self::new(w, h);
 
         for (pt, cell) in points {
             let x = (pt.x + x_offset) as usize;
@@ -144,27 +212,43 @@ impl<T> Grid<T> {
     }
 
     pub fn neighbours4(&self, c: impl Coord) -> Vec<&T> {
-        self.neighbours_coords4(c)
+        self.neighbours4_with(c, Edge::Skip)
+    }
+
+    pub fn neighbours4_with(&self, c: impl Coord, edge: Edge) -> Vec<&T> {
+        self.neighbours_coords4_with(c, edge)
             .iter()
             .flat_map(|&c| self.get(c))
             .collect()
     }
 
     pub fn neighbours_coords4(&self, c: impl Coord) -> Vec<(usize, usize)> {
+        self.neighbours_coords4_with(c, Edge::Skip)
+    }
+
+    pub fn neighbours_coords4_with(&self, c: impl Coord, edge: Edge) -> Vec<(usize, usize)> {
         [(-1, 0), (0, -1), (0, 1), (1, 0)]
             .iter()
-            .flat_map(|&(dx, dy)| self.neighbour_coords(&c, dx, dy))
+            .flat_map(|&(dx, dy)| self.neighbour_coords(&c, dx, dy, edge))
             .collect()
     }
 
     pub fn neighbours8(&self, c: impl Coord) -> Vec<&T> {
-        self.neighbours_coords8(c)
+        self.neighbours8_with(c, Edge::Skip)
+    }
+
+    pub fn neighbours8_with(&self, c: impl Coord, edge: Edge) -> Vec<&T> {
+        self.neighbours_coords8_with(c, edge)
             .iter()
             .flat_map(|&c| self.get(c))
             .collect()
     }
 
     pub fn neighbours_coords8(&self, c: impl Coord) -> Vec<(usize, usize)> {
+        self.neighbours_coords8_with(c, Edge::Skip)
+    }
+
+    pub fn neighbours_coords8_with(&self, c: impl Coord, edge: Edge) -> Vec<(usize, usize)> {
         [
             (-1, -1),
             (-1, 0),
@@ -176,33 +260,75 @@ impl<T> Grid<T> {
             (1, 1),
         ]
         .iter()
-        .flat_map(|&(dx, dy)| self.neighbour_coords(&c, dx, dy))
+        .flat_map(|&(dx, dy)| self.neighbour_coords(&c, dx, dy, edge))
         .collect()
     }
 
-    fn neighbour_coords(&self, c: &impl Coord, dx: isize, dy: isize) -> Option<(usize, usize)> {
-        if (c.x() == 0 && dx == -1) || (c.y() == 0 && dy == -1) {
-            None
-        } else {
-            Some((
-                ((c.x() as isize) + dx) as usize,
-                ((c.y() as isize) + dy) as usize,
-            ))
+    pub fn neighbours_coords_with(
+        &self,
+        c: impl Coord,
+        connectivity: Connectivity,
+        edge: Edge,
+    ) -> Vec<(usize, usize)> {
+        match connectivity {
+            Connectivity::Four => self.neighbours_coords4_with(c, edge),
+            Connectivity::Eight => self.neighbours_coords8_with(c, edge),
+        }
+    }
+
+    pub fn neighbour_coords(
+        &self,
+        c: &impl Coord,
+        dx: isize,
+        dy: isize,
+        edge: Edge,
+    ) -> Option<(usize, usize)> {
+        if self.w == 0 || self.h == 0 {
+            return None;
+        }
+
+        let (x, y) = c.coords();
+        let x = x as isize + dx;
+        let y = y as isize + dy;
+        let (w, h) = (self.w as isize, self.h as isize);
+
+        match edge {
+            Edge::Skip => {
+                if (0..w).contains(&x) && (0..h).contains(&y) {
+                    Some((x as usize, y as usize))
+                } else {
+                    None
+                }
+            }
+            Edge::Clamp => Some((x.clamp(0, w - 1) as usize, y.clamp(0, h - 1) as usize)),
+            Edge::Wrap => Some((x.rem_euclid(w) as usize, y.rem_euclid(h) as usize)),
         }
     }
 
     pub fn iter_row(&self, row: usize) -> RowIter<'_, T> {
+        self.iter_row_from(row, 0)
+    }
+
+    // iterates over a full row starting at column `start`, wrapping around the right edge
+    pub fn iter_row_from(&self, row: usize, start: usize) -> RowIter<'_, T> {
         RowIter {
             grid: self,
             row,
+            start,
             pos: 0,
         }
     }
 
     pub fn iter_col(&self, col: usize) -> ColIter<'_, T> {
+        self.iter_col_from(col, 0)
+    }
+
+    // iterates over a full column starting at row `start`, wrapping around the bottom edge
+    pub fn iter_col_from(&self, col: usize, start: usize) -> ColIter<'_, T> {
         ColIter {
             grid: self,
             col,
+            start,
             pos: 0,
         }
     }
@@ -214,13 +340,69 @@ impl<T> Grid<T> {
     pub fn as_slice_mut(&mut self) -> &mut [T] {
         &mut self.cells
     }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn rows(&self) -> Chunks<'_, T> {
+        self.cells.chunks(self.w.max(1))
+    }
+
+    pub fn rows_mut(&mut self) -> ChunksMut<'_, T> {
+        self.cells.chunks_mut(self.w.max(1))
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = ColIter<'_, T>> {
+        (0..self.w).map(move |x| self.iter_col(x))
+    }
+
+    pub fn enumerate_cells(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let w = self.w;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, c)| ((i % w, i / w), c))
+    }
+
+    pub fn enumerate_cells_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut T)> {
+        let w = self.w;
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, c)| ((i % w, i / w), c))
+    }
+
+    // cells from `c` (included) in the given direction, until the border of the grid
+    pub fn iter_ray(&self, c: impl Coord, dx: isize, dy: isize) -> RayIter<'_, T> {
+        RayIter {
+            grid: self,
+            pos: Some(c.coords()),
+            dx,
+            dy,
+        }
+    }
+
+    // cells from `c` going down and right
+    pub fn iter_diag(&self, c: impl Coord) -> RayIter<'_, T> {
+        self.iter_ray(c, 1, 1)
+    }
+
+    // cells from `c` going down and left
+    pub fn iter_anti_diag(&self, c: impl Coord) -> RayIter<'_, T> {
+        self.iter_ray(c, -1, 1)
+    }
 }
 
 impl<T> FromStr for Grid<T>
 where
     T: TryFrom<u8>,
 {
-    type Err = T::Error;
+    type Err = GridError<T::Error>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Grid::from_reader(s.as_bytes())
@@ -242,6 +424,103 @@ where
     }
 }
 
+#[derive(Debug, Eq, PartialEq)]
+pub enum GridError<E> {
+    // a row doesn't have as many cells as the first one
+    Ragged {
+        row: usize,
+        width: usize,
+        expected: usize,
+    },
+    // a cell couldn't be parsed
+    Cell {
+        row: usize,
+        col: usize,
+        err: E,
+    },
+}
+
+impl<E> Display for GridError<E>
+where
+    E: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            GridError::Ragged {
+                row,
+                width,
+                expected,
+            } => write!(
+                f,
+                "row {} has {} cells, expected {} like the first row",
+                row, width, expected
+            ),
+            GridError::Cell { row, col, err } => {
+                write!(f, "invalid cell at row {}, column {}: {:?}", row, col, err)
+            }
+        }
+    }
+}
+
+impl<E> std::error::Error for GridError<E> where E: Debug {}
+
+// how neighbour queries behave at the border of the grid
+#[allow(unused)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Edge {
+    // out of bounds neighbours are dropped
+    Skip,
+    // out of bounds neighbours are moved back onto the nearest border cell
+    Clamp,
+    // the grid is a torus, out of bounds neighbours reappear on the opposite side
+    Wrap,
+}
+
+#[allow(unused)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Connectivity {
+    Four,
+    Eight,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Rect {
+    pub x: usize,
+    pub y: usize,
+    pub w: usize,
+    pub h: usize,
+}
+
+#[allow(unused)]
+impl Rect {
+    pub fn new(x: usize, y: usize, w: usize, h: usize) -> Self {
+        Self { x, y, w, h }
+    }
+
+    pub fn contains(&self, c: &impl Coord) -> bool {
+        (self.x..self.x + self.w).contains(&c.x()) && (self.y..self.y + self.h).contains(&c.y())
+    }
+
+    // smallest rectangle containing both this one and the given point
+    pub fn extend(&mut self, c: &impl Coord) {
+        if self.w == 0 || self.h == 0 {
+            *self = Self::new(c.x(), c.y(), 1, 1);
+            return;
+        }
+
+        let x2 = (self.x + self.w).max(c.x() + 1);
+        let y2 = (self.y + self.h).max(c.y() + 1);
+        self.x = self.x.min(c.x());
+        self.y = self.y.min(c.y());
+        self.w = x2 - self.x;
+        self.h = y2 - self.y;
+    }
+
+    pub fn area(&self) -> usize {
+        self.w * self.h
+    }
+}
+
 pub trait Coord {
     fn x(&self) -> usize;
     fn y(&self) -> usize;
@@ -290,15 +569,10 @@ impl Coord for (usize, usize) {
     }
 }
 
-#[derive(Clone, Debug)]
-pub struct Point {
-    x: i64,
-    y: i64,
-}
-
 pub struct ColIter<'a, T> {
     grid: &'a Grid<T>,
     col: usize,
+    start: usize,
     pos: usize,
 }
 
@@ -306,15 +580,40 @@ impl<'a, T> Iterator for ColIter<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let val = self.grid.get((self.col, self.pos))?;
+        if self.pos >= self.grid.h {
+            return None;
+        }
+        let y = (self.start + self.pos) % self.grid.h;
+        let val = self.grid.get((self.col, y))?;
         self.pos += 1;
         Some(val)
     }
 }
 
+pub struct RayIter<'a, T> {
+    grid: &'a Grid<T>,
+    pos: Option<(usize, usize)>,
+    dx: isize,
+    dy: isize,
+}
+
+impl<'a, T> Iterator for RayIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.pos?;
+        let val = self.grid.get(pos)?;
+        self.pos = self
+            .grid
+            .neighbour_coords(&pos, self.dx, self.dy, Edge::Skip);
+        Some(val)
+    }
+}
+
 pub struct RowIter<'a, T> {
     grid: &'a Grid<T>,
     row: usize,
+    start: usize,
     pos: usize,
 }
 
@@ -322,8 +621,83 @@ impl<'a, T> Iterator for RowIter<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let val = self.grid.get((self.pos, self.row))?;
+        if self.pos >= self.grid.w {
+            return None;
+        }
+        let x = (self.start + self.pos) % self.grid.w;
+        let val = self.grid.get((x, self.row))?;
         self.pos += 1;
         Some(val)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<u8> {
+        Grid::from_reader_callback("123\n456\n789".as_bytes(), |b| Ok::<_, ()>(b - b'0')).unwrap()
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let r = Grid::from_reader_callback("123\n45\n678".as_bytes(), |b| Ok::<_, ()>(b - b'0'));
+        assert_eq!(
+            r.err(),
+            Some(GridError::Ragged {
+                row: 1,
+                width: 2,
+                expected: 3
+            })
+        );
+
+        let g =
+            Grid::<u8>::from_split_whitespace_reader_padded("1 2\n3 4 5\n6".as_bytes()).unwrap();
+        assert_eq!((g.w, g.h), (3, 3));
+        assert_eq!(g.as_slice(), &[1, 2, 0, 3, 4, 5, 6, 0, 0]);
+    }
+
+    #[test]
+    fn neighbours_skip() {
+        let g = grid();
+        assert_eq!(g.neighbours_coords4((0, 0)), vec![(0, 1), (1, 0)]);
+        assert_eq!(g.neighbours_coords8((2, 2)).len(), 3);
+    }
+
+    #[test]
+    fn neighbours_clamp() {
+        let g = grid();
+        assert_eq!(
+            g.neighbours4_with((0, 0), Edge::Clamp),
+            vec![&1, &1, &4, &2]
+        );
+    }
+
+    #[test]
+    fn neighbours_wrap() {
+        let g = grid();
+        assert_eq!(g.neighbours4_with((0, 0), Edge::Wrap), vec![&3, &7, &4, &2]);
+        assert_eq!(g.neighbours8_with((2, 2), Edge::Wrap).len(), 8);
+    }
+
+    #[test]
+    fn iter_rows_cols_diags() {
+        let g = grid();
+        assert_eq!(g.rows().nth(1), Some(&[4, 5, 6][..]));
+        assert_eq!(
+            g.cols().nth(2).unwrap().collect::<Vec<_>>(),
+            vec![&3, &6, &9]
+        );
+        assert_eq!(g.iter_diag((0, 0)).collect::<Vec<_>>(), vec![&1, &5, &9]);
+        assert_eq!(g.iter_anti_diag((2, 1)).collect::<Vec<_>>(), vec![&6, &8]);
+        assert_eq!(g.enumerate_cells().nth(5), Some(((2, 1), &6)));
+    }
+
+    #[test]
+    fn iter_wrapping() {
+        let g = grid();
+        assert_eq!(g.iter_row_from(1, 2).collect::<Vec<_>>(), vec![&6, &4, &5]);
+        assert_eq!(g.iter_col_from(0, 1).collect::<Vec<_>>(), vec![&4, &7, &1]);
+        assert_eq!(g.iter_row(3).count(), 0);
+    }
+}
@@ -0,0 +1,138 @@
+use crate::grid::{Grid, GridError};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+
+// positions of the marker characters found while parsing a grid
+pub type Markers = HashMap<char, Vec<(usize, usize)>>;
+
+#[allow(unused)]
+impl<T> Grid<T> {
+    // parses a grid like `from_reader_callback`, additionally recording the positions of
+    // any of the `markers` characters (which are still passed to `f` to get their cell).
+    // All rows must have the same width.
+    pub fn from_reader_with_markers<R, F, E>(
+        r: R,
+        markers: &str,
+        mut f: F,
+    ) -> Result<(Self, Markers), GridError<E>>
+    where
+        R: Read,
+        F: FnMut(u8) -> Result<T, E>,
+    {
+        let mut rows = vec![];
+        let mut found = Markers::new();
+
+        for (y, line) in BufReader::new(r).lines().map_while(Result::ok).enumerate() {
+            let mut row = Vec::with_capacity(line.len());
+            for (x, b) in line.bytes().enumerate() {
+                if markers.as_bytes().contains(&b) {
+                    found.entry(b as char).or_default().push((x, y));
+                }
+                row.push(f(b).map_err(|err| GridError::Cell {
+                    row: y,
+                    col: x,
+                    err,
+                })?);
+            }
+            rows.push(row);
+        }
+
+        let grid = Grid::from_rows(rows, None)?;
+        Ok((grid, found))
+    }
+}
+
+#[allow(unused)]
+#[derive(Debug, Eq, PartialEq)]
+pub struct InvalidCell(pub char);
+
+// declares a fieldless enum of grid cells, each variant being associated to a character,
+// along with its conversions from bytes and to chars and its display
+#[allow(unused_macros)]
+macro_rules! cell_enum {
+    ($(#[$meta:meta])* $vis:vis enum $name:ident { $($variant:ident = $c:literal),+ $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+        $vis enum $name {
+            $($variant),+
+        }
+
+        impl TryFrom<u8> for $name {
+            type Error = $crate::grid_parse::InvalidCell;
+
+            fn try_from(b: u8) -> Result<Self, Self::Error> {
+                match b as char {
+                    $($c => Ok($name::$variant),)+
+                    c => Err($crate::grid_parse::InvalidCell(c)),
+                }
+            }
+        }
+
+        impl From<$name> for char {
+            fn from(cell: $name) -> char {
+                match cell {
+                    $($name::$variant => $c),+
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", char::from(*self))
+            }
+        }
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use cell_enum;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    cell_enum! {
+        enum Tile {
+            Open = '.',
+            Wall = '#',
+        }
+    }
+
+    #[test]
+    fn markers() {
+        let (g, m) = Grid::from_reader_with_markers("S..\n.#E".as_bytes(), "SE", |b| match b {
+            b'S' | b'E' => Tile::try_from(b'.'),
+            b => Tile::try_from(b),
+        })
+        .unwrap();
+
+        assert_eq!((g.w, g.h), (3, 2));
+        assert_eq!(g.get((1, 1)), Some(&Tile::Wall));
+        assert_eq!(m.get(&'S'), Some(&vec![(0, 0)]));
+        assert_eq!(m.get(&'E'), Some(&vec![(2, 1)]));
+        assert_eq!(g.to_string(), "...\n.#.\n");
+    }
+
+    #[test]
+    fn ragged_and_invalid() {
+        let r = Grid::<Tile>::from_reader_with_markers("..\n...".as_bytes(), "", Tile::try_from);
+        assert_eq!(
+            r.err(),
+            Some(GridError::Ragged {
+                row: 1,
+                width: 3,
+                expected: 2
+            })
+        );
+
+        let r = Grid::<Tile>::from_reader_with_markers("..\n.x".as_bytes(), "", Tile::try_from);
+        assert_eq!(
+            r.err(),
+            Some(GridError::Cell {
+                row: 1,
+                col: 1,
+                err: InvalidCell('x')
+            })
+        );
+    }
+}
@@ -0,0 +1,278 @@
+use std::collections::HashMap;
+use std::iter::repeat_n;
+
+// which neighbours of a cell are adjacent, depending on what they share with the cell:
+// a face (one coordinate differs), at least an edge (at most two coordinates differ),
+// or at least a corner (any coordinate may differ)
+#[allow(unused)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Adjacency {
+    Face,
+    Edge,
+    Corner,
+}
+
+impl Adjacency {
+    fn max_changes(&self) -> usize {
+        match self {
+            Adjacency::Face => 1,
+            Adjacency::Edge => 2,
+            Adjacency::Corner => usize::MAX,
+        }
+    }
+}
+
+// all offsets in {-1, 0, 1}^D to the adjacent cells
+fn offsets<const D: usize>(adjacency: Adjacency) -> Vec<[i64; D]> {
+    let max_changes = adjacency.max_changes();
+    (0..3usize.pow(D as u32))
+        .map(|mut n| {
+            let mut o = [0; D];
+            for d in o.iter_mut() {
+                *d = (n % 3) as i64 - 1;
+                n /= 3;
+            }
+            o
+        })
+        .filter(|o| {
+            let changes = o.iter().filter(|&&d| d != 0).count();
+            changes > 0 && changes <= max_changes
+        })
+        .collect()
+}
+
+// dense grid with D dimensions, the first coordinate varying the fastest
+#[derive(Clone, Debug)]
+pub struct GridN<T, const D: usize> {
+    cells: Vec<T>,
+    dims: [usize; D],
+}
+
+#[allow(unused)]
+pub type Grid3<T> = GridN<T, 3>;
+
+#[allow(unused)]
+impl<T, const D: usize> GridN<T, D> {
+    pub fn new(dims: [usize; D]) -> Self
+    where
+        T: Clone + Default,
+    {
+        Self::new_with(dims, Default::default())
+    }
+
+    pub fn new_with(dims: [usize; D], val: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: repeat_n(val, dims.iter().product()).collect(),
+            dims,
+        }
+    }
+
+    pub fn dims(&self) -> [usize; D] {
+        self.dims
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains_coord(&self, c: &[usize; D]) -> bool {
+        c.iter().zip(&self.dims).all(|(x, d)| x < d)
+    }
+
+    fn index(&self, c: &[usize; D]) -> Option<usize> {
+        if !self.contains_coord(c) {
+            return None;
+        }
+        Some(
+            c.iter()
+                .zip(&self.dims)
+                .rev()
+                .fold(0, |i, (x, d)| i * d + x),
+        )
+    }
+
+    fn coords(&self, mut i: usize) -> [usize; D] {
+        let mut c = [0; D];
+        for (x, d) in c.iter_mut().zip(&self.dims) {
+            *x = i % d;
+            i /= d;
+        }
+        c
+    }
+
+    pub fn get(&self, c: [usize; D]) -> Option<&T> {
+        self.index(&c).and_then(|i| self.cells.get(i))
+    }
+
+    pub fn get_mut(&mut self, c: [usize; D]) -> Option<&mut T> {
+        self.index(&c).and_then(move |i| self.cells.get_mut(i))
+    }
+
+    pub fn neighbours_coords(&self, c: [usize; D], adjacency: Adjacency) -> Vec<[usize; D]> {
+        offsets::<D>(adjacency)
+            .into_iter()
+            .filter_map(|o| {
+                let mut n = [0; D];
+                for k in 0..D {
+                    let x = c[k] as i64 + o[k];
+                    if x < 0 || x >= self.dims[k] as i64 {
+                        return None;
+                    }
+                    n[k] = x as usize;
+                }
+                Some(n)
+            })
+            .collect()
+    }
+
+    pub fn neighbours(&self, c: [usize; D], adjacency: Adjacency) -> Vec<&T> {
+        self.neighbours_coords(c, adjacency)
+            .into_iter()
+            .flat_map(|n| self.get(n))
+            .collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn enumerate_cells(&self) -> impl Iterator<Item = ([usize; D], &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, c)| (self.coords(i), c))
+    }
+}
+
+// sparse grid with D dimensions and signed, unbounded coordinates
+#[derive(Clone, Debug)]
+pub struct SparseGridN<T, const D: usize> {
+    cells: HashMap<[i64; D], T>,
+}
+
+#[allow(unused)]
+pub type SparseGrid3<T> = SparseGridN<T, 3>;
+
+impl<T, const D: usize> Default for SparseGridN<T, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(unused)]
+impl<T, const D: usize> SparseGridN<T, D> {
+    pub fn new() -> Self {
+        Self {
+            cells: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, c: &[i64; D]) -> Option<&T> {
+        self.cells.get(c)
+    }
+
+    pub fn get_mut(&mut self, c: &[i64; D]) -> Option<&mut T> {
+        self.cells.get_mut(c)
+    }
+
+    pub fn insert(&mut self, c: [i64; D], val: T) -> Option<T> {
+        self.cells.insert(c, val)
+    }
+
+    pub fn remove(&mut self, c: &[i64; D]) -> Option<T> {
+        self.cells.remove(c)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&[i64; D], &T)> {
+        self.cells.iter()
+    }
+
+    pub fn neighbours_coords(c: [i64; D], adjacency: Adjacency) -> Vec<[i64; D]> {
+        offsets::<D>(adjacency)
+            .into_iter()
+            .map(|o| {
+                let mut n = c;
+                for (x, d) in n.iter_mut().zip(o) {
+                    *x += d;
+                }
+                n
+            })
+            .collect()
+    }
+
+    pub fn neighbours(&self, c: [i64; D], adjacency: Adjacency) -> Vec<&T> {
+        Self::neighbours_coords(c, adjacency)
+            .iter()
+            .flat_map(|n| self.cells.get(n))
+            .collect()
+    }
+
+    // smallest and largest coordinates along each axis, if there are any cells
+    pub fn bounds(&self) -> Option<([i64; D], [i64; D])> {
+        let mut keys = self.cells.keys();
+        let first = *keys.next()?;
+        Some(keys.fold((first, first), |(mut min, mut max), c| {
+            for k in 0..D {
+                min[k] = min[k].min(c[k]);
+                max[k] = max[k].max(c[k]);
+            }
+            (min, max)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offsets_count() {
+        assert_eq!(offsets::<3>(Adjacency::Face).len(), 6);
+        assert_eq!(offsets::<3>(Adjacency::Edge).len(), 18);
+        assert_eq!(offsets::<3>(Adjacency::Corner).len(), 26);
+        assert_eq!(offsets::<4>(Adjacency::Corner).len(), 80);
+    }
+
+    #[test]
+    fn dense_grid() {
+        let mut g = Grid3::new_with([3, 4, 5], 0u8);
+        if let Some(c) = g.get_mut([2, 3, 4]) {
+            *c = 1;
+        }
+        assert_eq!(
+            g.enumerate_cells().find(|(_, &c)| c == 1).map(|(c, _)| c),
+            Some([2, 3, 4])
+        );
+        assert_eq!(g.neighbours_coords([0, 0, 0], Adjacency::Face).len(), 3);
+        assert_eq!(g.neighbours([1, 2, 3], Adjacency::Corner).len(), 26);
+        assert_eq!(g.get([3, 0, 0]), None);
+    }
+
+    #[test]
+    fn sparse_grid() {
+        let mut g = SparseGrid3::new();
+        g.insert([-1, 0, 5], true);
+        g.insert([2, -3, 0], true);
+        assert_eq!(g.bounds(), Some(([-1, -3, 0], [2, 0, 5])));
+        assert_eq!(g.neighbours([0, 0, 5], Adjacency::Face), vec![&true]);
+    }
+}
@@ -0,0 +1,239 @@
+use crate::grid::Grid;
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+#[allow(unused)]
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    pub fn gray(v: u8) -> Self {
+        Self(v, v, v)
+    }
+}
+
+#[allow(unused)]
+impl<T> Grid<T> {
+    // binary PBM (P4), `f` tells whether a cell is black
+    pub fn write_pbm<W, F>(&self, mut w: W, scale: usize, f: F) -> io::Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> bool,
+    {
+        let (width, height) = (self.w * scale, self.h * scale);
+        write!(w, "P4\n{} {}\n", width, height)?;
+
+        for row in self.scaled_rows(scale, f) {
+            // pack 8 pixels per byte, most significant bit first, rows padded to a byte
+            for chunk in row.chunks(8) {
+                let byte = chunk
+                    .iter()
+                    .enumerate()
+                    .fold(0u8, |b, (i, &on)| if on { b | (0x80 >> i) } else { b });
+                w.write_all(&[byte])?;
+            }
+        }
+        Ok(())
+    }
+
+    // binary PGM (P5) with 8-bit gray levels
+    pub fn write_pgm<W, F>(&self, mut w: W, scale: usize, f: F) -> io::Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> u8,
+    {
+        let (width, height) = (self.w * scale, self.h * scale);
+        write!(w, "P5\n{} {}\n255\n", width, height)?;
+
+        for row in self.scaled_rows(scale, f) {
+            w.write_all(&row)?;
+        }
+        Ok(())
+    }
+
+    // binary PPM (P6) with 8-bit channels
+    pub fn write_ppm<W, F>(&self, mut w: W, scale: usize, f: F) -> io::Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> Rgb,
+    {
+        let (width, height) = (self.w * scale, self.h * scale);
+        write!(w, "P6\n{} {}\n255\n", width, height)?;
+
+        for row in self.scaled_rows(scale, f) {
+            for Rgb(r, g, b) in row {
+                w.write_all(&[r, g, b])?;
+            }
+        }
+        Ok(())
+    }
+
+    // 8-bit RGB PNG, without compression
+    pub fn write_png<W, F>(&self, mut w: W, scale: usize, f: F) -> io::Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> Rgb,
+    {
+        let (width, height) = (self.w * scale, self.h * scale);
+
+        // raw image data: each scanline starts with its filter type (none)
+        let mut raw = Vec::with_capacity(height * (width * 3 + 1));
+        for row in self.scaled_rows(scale, f) {
+            raw.push(0);
+            for Rgb(r, g, b) in row {
+                raw.extend_from_slice(&[r, g, b]);
+            }
+        }
+
+        let mut header = vec![];
+        header.extend_from_slice(&(width as u32).to_be_bytes());
+        header.extend_from_slice(&(height as u32).to_be_bytes());
+        // bit depth, color type (RGB), compression, filter, interlace
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        w.write_all(b"\x89PNG\r\n\x1a\n")?;
+        write_chunk(&mut w, b"IHDR", &header)?;
+        write_chunk(&mut w, b"IDAT", &zlib_stored(&raw))?;
+        write_chunk(&mut w, b"IEND", &[])?;
+        Ok(())
+    }
+
+    // writes an RGB image, the format being chosen from the file extension
+    pub fn save_image<P, F>(&self, p: P, scale: usize, f: F) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+        F: Fn(&T) -> Rgb,
+    {
+        let p = p.as_ref();
+        let w = BufWriter::new(File::create(p)?);
+        match p.extension().and_then(|e| e.to_str()) {
+            Some("png") => self.write_png(w, scale, f),
+            Some("ppm") => self.write_ppm(w, scale, f),
+            Some("pgm") => self.write_pgm(w, scale, |c| luma(f(c))),
+            Some("pbm") => self.write_pbm(w, scale, |c| luma(f(c)) < 128),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "unsupported image extension",
+            )),
+        }
+    }
+
+    // rows of pixels, each cell being repeated `scale` times in both directions
+    pub fn scaled_rows<F, V>(&self, scale: usize, f: F) -> Vec<Vec<V>>
+    where
+        F: Fn(&T) -> V,
+        V: Clone,
+    {
+        let mut rows = Vec::with_capacity(self.h * scale);
+        for y in 0..self.h {
+            let row = self
+                .iter_row(y)
+                .flat_map(|c| std::iter::repeat_n(f(c), scale))
+                .collect::<Vec<_>>();
+            for _ in 0..scale {
+                rows.push(row.clone());
+            }
+        }
+        rows
+    }
+}
+
+fn luma(Rgb(r, g, b): Rgb) -> u8 {
+    ((r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000) as u8
+}
+
+fn write_chunk<W: Write>(w: &mut W, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    w.write_all(&(data.len() as u32).to_be_bytes())?;
+    w.write_all(kind)?;
+    w.write_all(data)?;
+
+    let crc = crc32(kind.iter().chain(data));
+    w.write_all(&crc.to_be_bytes())
+}
+
+// zlib stream made of uncompressed deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        // an empty stream still needs one final block
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(last as u8);
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32<'a>(data: impl IntoIterator<Item = &'a u8>) -> u32 {
+    let mut table = [0u32; 256];
+    for (n, e) in table.iter_mut().enumerate() {
+        let mut c = n as u32;
+        for _ in 0..8 {
+            c = if c & 1 == 1 {
+                0xedb88320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+        }
+        *e = c;
+    }
+
+    !data.into_iter().fold(!0u32, |c, &b| {
+        table[((c ^ b as u32) & 0xff) as usize] ^ (c >> 8)
+    })
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &d| {
+        let a = (a + d as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae426082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e60398);
+    }
+
+    #[test]
+    fn pbm_scaled() {
+        let g =
+            Grid::from_reader_callback("#.\n.#".as_bytes(), |b| Ok::<_, ()>(b == b'#')).unwrap();
+        let mut out = vec![];
+        g.write_pbm(&mut out, 2, |&b| b).unwrap();
+        assert_eq!(out, b"P4\n4 4\n\xc0\xc0\x30\x30");
+    }
+
+    #[test]
+    fn png_structure() {
+        let g = Grid::new_with(3, 2, 0u8);
+        let mut out = vec![];
+        g.write_png(&mut out, 1, |&v| Rgb::gray(v)).unwrap();
+
+        assert_eq!(&out[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&out[12..16], b"IHDR");
+        assert_eq!(&out[out.len() - 8..out.len() - 4], b"IEND");
+        // signature + IHDR + IDAT (2 rows of 1 + 9 bytes, stored) + IEND
+        assert_eq!(out.len(), 8 + 25 + (12 + 2 + 5 + 20 + 4) + 12);
+    }
+}
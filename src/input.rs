@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs::File;
+use std::io::{self, Read};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+// raw bytes of an input file, memory mapped when asked to with `AOC_MMAP=1`
+pub enum InputBytes {
+    Owned(Vec<u8>),
+    #[cfg(target_os = "linux")]
+    Mapped(mmap::Mmap),
+}
+
+impl Deref for InputBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            InputBytes::Owned(v) => v,
+            #[cfg(target_os = "linux")]
+            InputBytes::Mapped(m) => m.as_slice(),
+        }
+    }
+}
+
+static CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<InputBytes>>>> = OnceLock::new();
+
+// loads an input file once, later calls for the same path sharing the same bytes
+pub fn load<P: AsRef<Path>>(p: P) -> io::Result<Arc<InputBytes>> {
+    let mut cache = CACHE
+        .get_or_init(Default::default)
+        .lock()
+        .expect("poisoned input cache");
+
+    let p = p.as_ref();
+    if let Some(bytes) = cache.get(p) {
+        return Ok(bytes.clone());
+    }
+
+    let bytes = Arc::new(read_file(p)?);
+    cache.insert(p.to_path_buf(), bytes.clone());
+    Ok(bytes)
+}
+
+// mapping is opt-in: a mapped file truncated by someone else while in use makes any access to
+// the missing pages crash the process with SIGBUS instead of failing gracefully
+#[cfg(target_os = "linux")]
+fn mmap_enabled() -> bool {
+    env::var_os("AOC_MMAP").is_some_and(|v| v == "1")
+}
+
+fn read_file(p: &Path) -> io::Result<InputBytes> {
+    let mut f = File::open(p)?;
+
+    #[cfg(target_os = "linux")]
+    if mmap_enabled() {
+        if let Some(m) = mmap::Mmap::map(&f) {
+            return Ok(InputBytes::Mapped(m));
+        }
+    }
+
+    let mut buf = vec![];
+    f.read_to_end(&mut buf)?;
+    Ok(InputBytes::Owned(buf))
+}
+
+#[cfg(target_os = "linux")]
+mod mmap {
+    use std::ffi::{c_int, c_long, c_void};
+    use std::fs::File;
+    use std::os::fd::AsRawFd;
+    use std::ptr;
+
+    const PROT_READ: c_int = 1;
+    const MAP_PRIVATE: c_int = 2;
+
+    // provided by the C library std already links against
+    extern "C" {
+        fn mmap(
+            addr: *mut c_void,
+            len: usize,
+            prot: c_int,
+            flags: c_int,
+            fd: c_int,
+            offset: c_long,
+        ) -> *mut c_void;
+        fn munmap(addr: *mut c_void, len: usize) -> c_int;
+    }
+
+    // read-only private mapping of a whole file
+    pub struct Mmap {
+        ptr: *mut c_void,
+        len: usize,
+    }
+
+    // the mapping is never written to, so it can be shared between threads
+    unsafe impl Send for Mmap {}
+    unsafe impl Sync for Mmap {}
+
+    impl Mmap {
+        // none if the file is empty or can't be mapped, in which case it should just be read
+        pub fn map(f: &File) -> Option<Self> {
+            let len = usize::try_from(f.metadata().ok()?.len()).ok()?;
+            if len == 0 {
+                return None;
+            }
+
+            let ptr = unsafe {
+                mmap(
+                    ptr::null_mut(),
+                    len,
+                    PROT_READ,
+                    MAP_PRIVATE,
+                    f.as_raw_fd(),
+                    0,
+                )
+            };
+            // MAP_FAILED is (void *) -1
+            (ptr as isize != -1).then_some(Self { ptr, len })
+        }
+
+        pub fn as_slice(&self) -> &[u8] {
+            unsafe { std::slice::from_raw_parts(self.ptr as *const u8, self.len) }
+        }
+    }
+
+    impl Drop for Mmap {
+        fn drop(&mut self) {
+            unsafe {
+                munmap(self.ptr, self.len);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn cached_loads() {
+        let p = std::env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+        fs::write(&p, "1,2,3\n").unwrap();
+
+        let a = load(&p).unwrap();
+        let b = load(&p).unwrap();
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(&b[..], b"1,2,3\n");
+        drop((a, b));
+        fs::remove_file(&p).unwrap();
+        assert!(load(p.with_extension("missing")).is_err());
+    }
+}
@@ -0,0 +1,384 @@
+use std::error::Error;
+use std::str::FromStr;
+
+// inclusive range of integers, empty when start > end
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+#[allow(unused)]
+impl Interval {
+    pub fn new(start: i64, end: i64) -> Self {
+        Self { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start > self.end
+    }
+
+    pub fn len(&self) -> u64 {
+        if self.is_empty() {
+            0
+        } else {
+            self.start.abs_diff(self.end) + 1
+        }
+    }
+
+    pub fn contains(&self, n: i64) -> bool {
+        self.start <= n && n <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        let i = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!i.is_empty()).then_some(i)
+    }
+
+    // single interval covering both, if they overlap or touch
+    pub fn union(&self, other: &Interval) -> Option<Interval> {
+        if self.is_empty() {
+            return Some(*other);
+        }
+        if other.is_empty() {
+            return Some(*self);
+        }
+        if self.start > other.end.saturating_add(1) || other.start > self.end.saturating_add(1) {
+            return None;
+        }
+        Some(Interval::new(
+            self.start.min(other.start),
+            self.end.max(other.end),
+        ))
+    }
+
+    // parts of this interval not in the other one, at most two
+    pub fn difference(&self, other: &Interval) -> Vec<Interval> {
+        let i = match self.intersection(other) {
+            Some(i) => i,
+            None if self.is_empty() => return vec![],
+            None => return vec![*self],
+        };
+
+        // nothing is left on a side where the intersection reaches the end of the integers
+        [
+            i.start
+                .checked_sub(1)
+                .map(|end| Interval::new(self.start, end)),
+            i.end
+                .checked_add(1)
+                .map(|start| Interval::new(start, self.end)),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|p| !p.is_empty())
+        .collect()
+    }
+}
+
+impl FromStr for Interval {
+    type Err = Box<dyn Error>;
+
+    // `a..b`, or a single number
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once("..") {
+            Some((a, b)) => Ok(Self::new(a.trim().parse()?, b.trim().parse()?)),
+            None => {
+                let n = s.trim().parse()?;
+                Ok(Self::new(n, n))
+            }
+        }
+    }
+}
+
+// union of disjoint intervals, kept sorted and merged
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+#[allow(unused)]
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    // number of integers in the set
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn contains(&self, n: i64) -> bool {
+        let i = self.intervals.partition_point(|i| i.end < n);
+        self.intervals.get(i).is_some_and(|i| i.contains(n))
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        if interval.is_empty() {
+            return;
+        }
+
+        let mut merged = interval;
+        let mut result = Vec::with_capacity(self.intervals.len() + 1);
+        for i in &self.intervals {
+            match merged.union(i) {
+                Some(u) => merged = u,
+                None => result.push(*i),
+            }
+        }
+        let pos = result.partition_point(|i| i.start < merged.start);
+        result.insert(pos, merged);
+        self.intervals = result;
+    }
+
+    pub fn remove(&mut self, interval: Interval) {
+        self.intervals = self
+            .intervals
+            .iter()
+            .flat_map(|i| i.difference(&interval))
+            .collect();
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        let mut s = self.clone();
+        for &i in &other.intervals {
+            s.insert(i);
+        }
+        s
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut s = IntervalSet::new();
+        for a in &self.intervals {
+            for b in &other.intervals {
+                if let Some(i) = a.intersection(b) {
+                    s.insert(i);
+                }
+            }
+        }
+        s
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut s = self.clone();
+        for &i in &other.intervals {
+            s.remove(i);
+        }
+        s
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        let mut s = IntervalSet::new();
+        for i in iter {
+            s.insert(i);
+        }
+        s
+    }
+}
+
+// axis-aligned box of integer points
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Cuboid {
+    pub x: Interval,
+    pub y: Interval,
+    pub z: Interval,
+}
+
+#[allow(unused)]
+impl Cuboid {
+    pub fn new(x: Interval, y: Interval, z: Interval) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.x.is_empty() || self.y.is_empty() || self.z.is_empty()
+    }
+
+    // wide enough for coordinates in the billions, whose volumes don't fit in 64 bits
+    pub fn volume(&self) -> u128 {
+        self.x.len() as u128 * self.y.len() as u128 * self.z.len() as u128
+    }
+
+    pub fn contains(&self, (x, y, z): (i64, i64, i64)) -> bool {
+        self.x.contains(x) && self.y.contains(y) && self.z.contains(z)
+    }
+
+    pub fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+        Some(Cuboid::new(
+            self.x.intersection(&other.x)?,
+            self.y.intersection(&other.y)?,
+            self.z.intersection(&other.z)?,
+        ))
+    }
+
+    // disjoint pieces (at most six) covering this cuboid minus the other one
+    pub fn subtract(&self, other: &Cuboid) -> Vec<Cuboid> {
+        let i = match self.intersection(other) {
+            Some(i) => i,
+            None if self.is_empty() => return vec![],
+            None => return vec![*self],
+        };
+
+        let mut pieces = vec![];
+        // slabs along x on both sides of the intersection, full height and depth
+        for x in self.x.difference(&i.x) {
+            pieces.push(Cuboid::new(x, self.y, self.z));
+        }
+        // then along y, restricted to the intersection's x range
+        for y in self.y.difference(&i.y) {
+            pieces.push(Cuboid::new(i.x, y, self.z));
+        }
+        // and finally along z, restricted to the intersection's x and y ranges
+        for z in self.z.difference(&i.z) {
+            pieces.push(Cuboid::new(i.x, i.y, z));
+        }
+        pieces
+    }
+}
+
+impl FromStr for Cuboid {
+    type Err = Box<dyn Error>;
+
+    // `x=a..b,y=c..d,z=e..f`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut x = None;
+        let mut y = None;
+        let mut z = None;
+        for part in s.split(',') {
+            let (axis, range) = part.trim().split_once('=').ok_or("missing '='")?;
+            let range = Some(range.parse()?);
+            match axis {
+                "x" => x = range,
+                "y" => y = range,
+                "z" => z = range,
+                _ => return Err("invalid axis".into()),
+            }
+        }
+        Ok(Cuboid::new(
+            x.ok_or("missing x")?,
+            y.ok_or("missing y")?,
+            z.ok_or("missing z")?,
+        ))
+    }
+}
+
+// union of disjoint cuboids
+#[derive(Clone, Debug, Default)]
+pub struct CuboidSet {
+    cuboids: Vec<Cuboid>,
+}
+
+#[allow(unused)]
+impl CuboidSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cuboids(&self) -> &[Cuboid] {
+        &self.cuboids
+    }
+
+    pub fn insert(&mut self, c: Cuboid) {
+        self.remove(&c);
+        if !c.is_empty() {
+            self.cuboids.push(c);
+        }
+    }
+
+    pub fn remove(&mut self, c: &Cuboid) {
+        self.cuboids = self.cuboids.iter().flat_map(|p| p.subtract(c)).collect();
+    }
+
+    pub fn volume(&self) -> u128 {
+        self.cuboids.iter().map(Cuboid::volume).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intervals() {
+        let a = Interval::new(0, 9);
+        let b = Interval::new(5, 14);
+        assert_eq!(a.len(), 10);
+        assert_eq!(a.intersection(&b), Some(Interval::new(5, 9)));
+        assert_eq!(a.union(&Interval::new(10, 12)), Some(Interval::new(0, 12)));
+        assert_eq!(a.union(&Interval::new(11, 12)), None);
+        assert_eq!(
+            a.difference(&Interval::new(3, 4)),
+            vec![Interval::new(0, 2), Interval::new(5, 9)]
+        );
+        assert_eq!("-3..7".parse::<Interval>().unwrap(), Interval::new(-3, 7));
+
+        let wide = Interval::new(i64::MIN, 0);
+        assert_eq!(
+            wide.difference(&Interval::new(i64::MIN, -1)),
+            vec![Interval::new(0, 0)]
+        );
+        assert_eq!(
+            Interval::new(0, i64::MAX).difference(&Interval::new(1, i64::MAX)),
+            vec![Interval::new(0, 0)]
+        );
+    }
+
+    #[test]
+    fn interval_sets() {
+        let mut s = [
+            Interval::new(0, 5),
+            Interval::new(10, 15),
+            Interval::new(4, 7),
+        ]
+        .into_iter()
+        .collect::<IntervalSet>();
+        assert_eq!(s.intervals(), &[Interval::new(0, 7), Interval::new(10, 15)]);
+        assert_eq!(s.len(), 14);
+
+        s.remove(Interval::new(2, 11));
+        assert_eq!(s.intervals(), &[Interval::new(0, 1), Interval::new(12, 15)]);
+        assert!(s.contains(13));
+        assert!(!s.contains(5));
+
+        let t = [Interval::new(1, 12)].into_iter().collect::<IntervalSet>();
+        assert_eq!(s.intersection(&t).len(), 2);
+        assert_eq!(s.difference(&t).len(), 4);
+        assert_eq!(s.union(&t).len(), 16);
+    }
+
+    #[test]
+    fn cuboids() {
+        // reactor reboot example
+        let mut set = CuboidSet::new();
+        set.insert("x=10..12,y=10..12,z=10..12".parse().unwrap());
+        set.insert("x=11..13,y=11..13,z=11..13".parse().unwrap());
+        set.remove(&"x=9..11,y=9..11,z=9..11".parse().unwrap());
+        set.insert("x=10..10,y=10..10,z=10..10".parse().unwrap());
+        assert_eq!(set.volume(), 39);
+
+        let a: Cuboid = "x=0..9,y=0..9,z=0..9".parse().unwrap();
+        let b: Cuboid = "x=3..5,y=3..5,z=3..5".parse().unwrap();
+        let pieces = a.subtract(&b);
+        assert_eq!(pieces.len(), 6);
+        assert_eq!(pieces.iter().map(Cuboid::volume).sum::<u128>(), 1000 - 27);
+
+        let huge: Cuboid = "x=-1000000000..1000000000,y=-1000000000..1000000000,z=0..1999999999"
+            .parse()
+            .unwrap();
+        assert_eq!(huge.volume(), 2_000_000_001 * 2_000_000_001 * 2_000_000_000);
+    }
+}
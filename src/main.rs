@@ -1,9 +1,27 @@
 use crate::solutions::exec_day;
 use std::env;
 
+mod automaton;
+mod bitgrid;
+mod bits;
+mod components;
+mod cycle;
+mod geometry;
+mod gif;
 mod grid;
+mod grid_parse;
+mod gridn;
+mod image;
+mod input;
+mod interval;
+mod memo;
+mod ocr;
+mod point;
+mod render;
+mod replay;
 mod solutions;
 mod solver;
+mod view;
 
 fn main() {
     let day = env::args()
@@ -11,5 +29,6 @@ fn main() {
         .unwrap_or_else(|| String::from("1"))
         .parse()
         .unwrap_or(1);
-    exec_day(day);
+    let debug = env::args().skip(2).any(|a| a == "--debug");
+    exec_day(day, debug);
 }
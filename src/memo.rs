@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// cache of already computed values of a function
+#[derive(Clone, Debug)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            cache: HashMap::new(),
+        }
+    }
+}
+
+#[allow(unused)]
+impl<K, V> Memo<K, V>
+where
+    K: Eq + Hash,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn get(&self, k: &K) -> Option<&V> {
+        self.cache.get(k)
+    }
+
+    pub fn insert(&mut self, k: K, v: V) {
+        self.cache.insert(k, v);
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    // cached value for the key, computing it first if needed;
+    // `f` gets the memo itself so that it can look up other values recursively
+    pub fn get_or_compute<F>(&mut self, k: K, f: F) -> V
+    where
+        K: Clone,
+        V: Clone,
+        F: FnOnce(&mut Self, &K) -> V,
+    {
+        if let Some(v) = self.cache.get(&k) {
+            return v.clone();
+        }
+        let v = f(self, &k);
+        self.cache.insert(k, v.clone());
+        v
+    }
+}
+
+// recursive function whose results are cached, see `memoize`
+pub struct Memoized<K, V, F> {
+    memo: Memo<K, V>,
+    f: F,
+}
+
+// wraps a recursive function so that it is evaluated at most once per argument:
+// instead of calling itself, `f` recurses through the callback it is given
+pub fn memoize<K, V, F>(f: F) -> Memoized<K, V, F>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    Memoized {
+        memo: Memo::new(),
+        f,
+    }
+}
+
+#[allow(unused)]
+impl<K, V, F> Memoized<K, V, F>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    pub fn call(&mut self, k: K) -> V {
+        Self::eval(&mut self.memo, &self.f, k)
+    }
+
+    pub fn memo(&self) -> &Memo<K, V> {
+        &self.memo
+    }
+
+    fn eval(memo: &mut Memo<K, V>, f: &F, k: K) -> V {
+        if let Some(v) = memo.get(&k) {
+            return v.clone();
+        }
+        let v = f(&mut |k| Self::eval(memo, f, k), k.clone());
+        memo.insert(k, v.clone());
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memoized_recursion() {
+        let mut fib = memoize(
+            |fib: &mut dyn FnMut(u64) -> u64, n| {
+                if n < 2 {
+                    n
+                } else {
+                    fib(n - 1) + fib(n - 2)
+                }
+            },
+        );
+        assert_eq!(fib.call(90), 2880067194370816120);
+        assert_eq!(fib.memo().len(), 91);
+    }
+
+    #[test]
+    fn get_or_compute() {
+        fn count(memo: &mut Memo<(u32, u32), u64>, k: &(u32, u32)) -> u64 {
+            // lattice paths to the corner of a w x h grid
+            match *k {
+                (0, _) | (_, 0) => 1,
+                (w, h) => {
+                    memo.get_or_compute((w - 1, h), count) + memo.get_or_compute((w, h - 1), count)
+                }
+            }
+        }
+
+        let mut memo = Memo::new();
+        assert_eq!(memo.get_or_compute((16, 16), count), 601080390);
+        assert_eq!(memo.get(&(2, 2)), Some(&6));
+    }
+}
@@ -0,0 +1,207 @@
+use crate::grid::Grid;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+// block letters used by AoC puzzles, 6 rows high (4 columns wide, except for Y)
+const FONT_6: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', ".###\n..#.\n..#.\n..#.\n..#.\n.###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+// block letters used by AoC puzzles, 10 rows high and 6 columns wide
+#[rustfmt::skip]
+const FONT_10: &[(char, &str)] = &[
+    ('A', "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#"),
+    ('B', "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####."),
+    ('C', ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####."),
+    ('E', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######"),
+    ('F', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+    ('G', ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#"),
+    ('H', "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#"),
+    ('J', "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###.."),
+    ('K', "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#"),
+    ('L', "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######"),
+    ('N', "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#"),
+    ('P', "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+    ('R', "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#"),
+    ('X', "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#"),
+    ('Z', "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######"),
+];
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum OcrError {
+    // the lit cells don't span a supported letter height
+    UnsupportedHeight(usize),
+    // letters which didn't match any glyph, as column ranges (start inclusive, end exclusive)
+    Unrecognized(Vec<(usize, usize)>),
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OcrError::UnsupportedHeight(h) => write!(f, "unsupported letter height: {}", h),
+            OcrError::Unrecognized(cols) => {
+                write!(f, "unrecognized glyphs at columns")?;
+                for (start, end) in cols {
+                    write!(f, " {}..{}", start, end)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for OcrError {}
+
+// reads the block letters formed by the cells for which `on` returns true
+pub fn recognize<T, F>(g: &Grid<T>, on: F) -> Result<String, OcrError>
+where
+    F: Fn(&T) -> bool,
+{
+    let lit = |x: usize, y: usize| g.get((x, y)).is_some_and(&on);
+
+    // only keep the rows containing lit cells
+    let rows = (0..g.h)
+        .filter(|&y| (0..g.w).any(|x| lit(x, y)))
+        .collect::<Vec<_>>();
+    let (top, height) = match (rows.first(), rows.last()) {
+        (Some(&first), Some(&last)) => (first, last - first + 1),
+        _ => return Ok(String::new()),
+    };
+
+    let font = match height {
+        6 => FONT_6,
+        10 => FONT_10,
+        h => return Err(OcrError::UnsupportedHeight(h)),
+    };
+
+    // letters are separated by blank columns
+    let blank = |x: usize| (top..top + height).all(|y| !lit(x, y));
+    let mut letters = vec![];
+    let mut x = 0;
+    while x < g.w {
+        if blank(x) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < g.w && !blank(x) {
+            x += 1;
+        }
+        letters.push((start, x));
+    }
+
+    let mut s = String::new();
+    let mut unrecognized = vec![];
+    for &(start, end) in &letters {
+        let glyph = (top..top + height)
+            .map(|y| {
+                (start..end)
+                    .map(|x| if lit(x, y) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+
+        match font
+            .iter()
+            .find(|(_, pattern)| matches_glyph(pattern, &glyph))
+        {
+            Some(&(c, _)) => s.push(c),
+            None => unrecognized.push((start, end)),
+        }
+    }
+
+    if unrecognized.is_empty() {
+        Ok(s)
+    } else {
+        Err(OcrError::Unrecognized(unrecognized))
+    }
+}
+
+// compares a font pattern to a glyph, ignoring the pattern's blank columns
+fn matches_glyph(pattern: &str, glyph: &[String]) -> bool {
+    let rows = pattern.lines().collect::<Vec<_>>();
+    let width = rows.first().map_or(0, |r| r.len());
+    let used = (0..width)
+        .filter(|&x| rows.iter().any(|r| r.as_bytes()[x] == b'#'))
+        .collect::<Vec<_>>();
+
+    rows.len() == glyph.len()
+        && rows.iter().zip(glyph).all(|(r, g)| {
+            g.len() == used.len()
+                && used
+                    .iter()
+                    .zip(g.bytes())
+                    .all(|(&x, b)| r.as_bytes()[x] == b)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(s: &str) -> Grid<bool> {
+        Grid::from_reader_callback(s.as_bytes(), |b| Ok::<_, ()>(b == b'#')).unwrap()
+    }
+
+    #[test]
+    fn recognize_6() {
+        let g = grid(
+            "\
+#..#.####..##..###.......
+#..#.#....#..#.#..#......
+####.###..#....#..#......
+#..#.#....#....###.......
+#..#.#....#..#.#.#.......
+#..#.####..##..#..#......",
+        );
+        assert_eq!(recognize(&g, |&b| b), Ok("HECR".to_string()));
+    }
+
+    #[test]
+    fn recognize_narrow_letters() {
+        let g = grid(
+            "\
+.###..#...#
+..#...#...#
+..#....#.#.
+..#.....#..
+..#.....#..
+.###....#..",
+        );
+        assert_eq!(recognize(&g, |&b| b), Ok("IY".to_string()));
+    }
+
+    #[test]
+    fn unrecognized_glyph() {
+        let g = grid(
+            "\
+####..#..#
+#..#..#..#
+#..#..####
+#..#..#..#
+#..#..#..#
+####..#..#",
+        );
+        assert_eq!(
+            recognize(&g, |&b| b),
+            Err(OcrError::Unrecognized(vec![(0, 4)]))
+        );
+    }
+}
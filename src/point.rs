@@ -0,0 +1,223 @@
+use crate::grid::Coord;
+use std::error::Error;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+// signed position on a plane, y growing downwards like grid rows
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+// signed displacement between two points
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Vec2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+#[allow(unused)]
+impl Point2 {
+    pub const ORIGIN: Point2 = Point2 { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn from_coord(c: impl Coord) -> Self {
+        Self::new(c.x() as i64, c.y() as i64)
+    }
+
+    // grid coordinates, if this point isn't negative
+    pub fn to_coord(self) -> Option<(usize, usize)> {
+        Some((usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?))
+    }
+
+    pub fn manhattan(self, other: Point2) -> u64 {
+        (other - self).manhattan_len()
+    }
+
+    pub fn chebyshev(self, other: Point2) -> u64 {
+        (other - self).chebyshev_len()
+    }
+
+    pub fn neighbours4(self) -> [Point2; 4] {
+        Vec2::DIRS4.map(|d| self + d)
+    }
+
+    pub fn neighbours8(self) -> [Point2; 8] {
+        Vec2::DIRS8.map(|d| self + d)
+    }
+}
+
+#[allow(unused)]
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2::new(0, 0);
+    pub const UP: Vec2 = Vec2::new(0, -1);
+    pub const DOWN: Vec2 = Vec2::new(0, 1);
+    pub const LEFT: Vec2 = Vec2::new(-1, 0);
+    pub const RIGHT: Vec2 = Vec2::new(1, 0);
+
+    pub const DIRS4: [Vec2; 4] = [Vec2::UP, Vec2::RIGHT, Vec2::DOWN, Vec2::LEFT];
+    pub const DIRS8: [Vec2; 8] = [
+        Vec2::new(0, -1),
+        Vec2::new(1, -1),
+        Vec2::new(1, 0),
+        Vec2::new(1, 1),
+        Vec2::new(0, 1),
+        Vec2::new(-1, 1),
+        Vec2::new(-1, 0),
+        Vec2::new(-1, -1),
+    ];
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan_len(self) -> u64 {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    pub fn chebyshev_len(self) -> u64 {
+        self.x.unsigned_abs().max(self.y.unsigned_abs())
+    }
+
+    // unit step in the direction of this vector on each axis
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    // quarter turn clockwise as seen on screen (up becomes right)
+    pub fn rotate_right(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    // quarter turn counter-clockwise as seen on screen (up becomes left)
+    pub fn rotate_left(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+}
+
+impl From<(usize, usize)> for Point2 {
+    fn from(c: (usize, usize)) -> Self {
+        Self::from_coord(c)
+    }
+}
+
+impl TryFrom<Point2> for (usize, usize) {
+    type Error = &'static str;
+
+    fn try_from(p: Point2) -> Result<Self, Self::Error> {
+        p.to_coord().ok_or("negative coordinates")
+    }
+}
+
+impl Add<Vec2> for Point2 {
+    type Output = Point2;
+
+    fn add(self, v: Vec2) -> Point2 {
+        Point2::new(self.x + v.x, self.y + v.y)
+    }
+}
+
+impl AddAssign<Vec2> for Point2 {
+    fn add_assign(&mut self, v: Vec2) {
+        *self = *self + v;
+    }
+}
+
+impl Sub<Vec2> for Point2 {
+    type Output = Point2;
+
+    fn sub(self, v: Vec2) -> Point2 {
+        Point2::new(self.x - v.x, self.y - v.y)
+    }
+}
+
+impl SubAssign<Vec2> for Point2 {
+    fn sub_assign(&mut self, v: Vec2) {
+        *self = *self - v;
+    }
+}
+
+impl Sub for Point2 {
+    type Output = Vec2;
+
+    fn sub(self, other: Point2) -> Vec2 {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, v: Vec2) -> Vec2 {
+        Vec2::new(self.x + v.x, self.y + v.y)
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, v: Vec2) -> Vec2 {
+        Vec2::new(self.x - v.x, self.y - v.y)
+    }
+}
+
+impl Mul<i64> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, n: i64) -> Vec2 {
+        Vec2::new(self.x * n, self.y * n)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+impl FromStr for Point2 {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut s = s.split(',');
+        let x = s.next().ok_or("invalid string")?.trim().parse()?;
+        let y = s.next().ok_or("invalid string")?.trim().parse()?;
+        Ok(Self { x, y })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(4, -2);
+        assert_eq!(b - a, Vec2::new(3, -4));
+        assert_eq!(a + (b - a) * 2, Point2::new(7, -6));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!((b - a).signum(), Vec2::new(1, -1));
+    }
+
+    #[test]
+    fn rotations() {
+        assert_eq!(Vec2::UP.rotate_right(), Vec2::RIGHT);
+        assert_eq!(Vec2::UP.rotate_left(), Vec2::LEFT);
+        assert_eq!(Vec2::RIGHT.rotate_right().rotate_right(), Vec2::LEFT);
+    }
+
+    #[test]
+    fn coords() {
+        assert_eq!(Point2::from((3, 4)).to_coord(), Some((3, 4)));
+        assert_eq!(Point2::new(-1, 0).to_coord(), None);
+        assert_eq!("5,-7".parse::<Point2>().unwrap(), Point2::new(5, -7));
+    }
+}
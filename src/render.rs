@@ -0,0 +1,200 @@
+use crate::grid::{Coord, Grid, Rect};
+use std::collections::HashMap;
+use std::fmt::{Display, Error, Formatter};
+
+#[allow(unused)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    Bold,
+}
+
+impl Color {
+    fn ansi(&self) -> &'static str {
+        match self {
+            Color::Red => "\x1b[31m",
+            Color::Green => "\x1b[32m",
+            Color::Yellow => "\x1b[33m",
+            Color::Blue => "\x1b[34m",
+            Color::Magenta => "\x1b[35m",
+            Color::Cyan => "\x1b[36m",
+            Color::Bold => "\x1b[1m",
+        }
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+
+// displays a grid with a custom representation of each cell,
+// optional highlighting, axis labels, and restricted to a viewport
+pub struct Renderer<'a, T, F> {
+    grid: &'a Grid<T>,
+    cell: F,
+    highlights: HashMap<(usize, usize), Color>,
+    axes: bool,
+    viewport: Option<Rect>,
+}
+
+#[allow(unused)]
+impl<T> Grid<T> {
+    pub fn render<F, S>(&self, cell: F) -> Renderer<'_, T, F>
+    where
+        F: Fn(&T) -> S,
+        S: Display,
+    {
+        Renderer {
+            grid: self,
+            cell,
+            highlights: HashMap::new(),
+            axes: false,
+            viewport: None,
+        }
+    }
+}
+
+#[allow(unused)]
+impl<'a, T, F, S> Renderer<'a, T, F>
+where
+    F: Fn(&T) -> S,
+    S: Display,
+{
+    // colors the given cells, later highlights take precedence over earlier ones
+    pub fn highlight<I, C>(mut self, coords: I, color: Color) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Coord,
+    {
+        for c in coords {
+            self.highlights.insert(c.coords(), color);
+        }
+        self
+    }
+
+    pub fn axes(mut self, axes: bool) -> Self {
+        self.axes = axes;
+        self
+    }
+
+    pub fn viewport(mut self, viewport: Rect) -> Self {
+        self.viewport = Some(viewport);
+        self
+    }
+
+    // visible part of the grid
+    fn window(&self) -> Rect {
+        let full = Rect::new(0, 0, self.grid.w, self.grid.h);
+        match self.viewport {
+            Some(v) => {
+                let x = v.x.min(full.w);
+                let y = v.y.min(full.h);
+                Rect::new(x, y, v.w.min(full.w - x), v.h.min(full.h - y))
+            }
+            None => full,
+        }
+    }
+
+    fn write_header(
+        &self,
+        f: &mut Formatter<'_>,
+        window: &Rect,
+        margin: usize,
+    ) -> Result<(), Error> {
+        let last = (window.x + window.w).saturating_sub(1);
+        let digits = last.to_string().len();
+
+        // one header line per digit, most significant first
+        for d in (0..digits).rev() {
+            write!(f, "{:margin$} ", "", margin = margin)?;
+            for x in window.x..window.x + window.w {
+                let p = 10usize.pow(d as u32);
+                if d == 0 || x >= p {
+                    write!(f, "{}", (x / p) % 10)?;
+                } else {
+                    write!(f, " ")?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T, F, S> Display for Renderer<'a, T, F>
+where
+    F: Fn(&T) -> S,
+    S: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        let window = self.window();
+        let margin = if self.axes {
+            (window.y + window.h).saturating_sub(1).to_string().len()
+        } else {
+            0
+        };
+
+        if self.axes {
+            self.write_header(f, &window, margin)?;
+        }
+
+        for y in window.y..window.y + window.h {
+            if self.axes {
+                write!(f, "{:>margin$} ", y, margin = margin)?;
+            }
+            for x in window.x..window.x + window.w {
+                if let Some(cell) = self.grid.get((x, y)) {
+                    match self.highlights.get(&(x, y)) {
+                        Some(color) => write!(f, "{}{}{}", color.ansi(), (self.cell)(cell), RESET)?,
+                        None => write!(f, "{}", (self.cell)(cell))?,
+                    }
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<u8> {
+        Grid::from_reader_callback("123\n456\n789".as_bytes(), |b| Ok::<_, ()>(b - b'0')).unwrap()
+    }
+
+    #[test]
+    fn render_cells() {
+        let g = grid();
+        let s = g
+            .render(|&v| if v % 2 == 0 { '#' } else { '.' })
+            .to_string();
+        assert_eq!(s, ".#.\n#.#\n.#.\n");
+    }
+
+    #[test]
+    fn render_viewport_and_axes() {
+        let g = grid();
+        let s = g
+            .render(|v| v.to_string())
+            .viewport(Rect::new(1, 1, 5, 5))
+            .axes(true)
+            .to_string();
+        assert_eq!(s, "  12\n1 56\n2 89\n");
+    }
+
+    #[test]
+    fn render_highlight() {
+        let g = grid();
+        let s = g
+            .render(|v| v.to_string())
+            .viewport(Rect::new(0, 0, 2, 1))
+            .highlight([(1, 0)], Color::Red)
+            .to_string();
+        assert_eq!(s, "1\x1b[31m2\x1b[0m\n");
+    }
+}
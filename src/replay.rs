@@ -0,0 +1,248 @@
+use crate::grid::Grid;
+use crate::render::Color;
+use std::fmt::Display;
+use std::io;
+use std::io::{BufRead, Write};
+use std::sync::mpsc::{channel, Receiver};
+use std::thread;
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+pub struct Frame<T> {
+    pub grid: Grid<T>,
+    pub highlights: Vec<(usize, usize)>,
+    pub label: String,
+}
+
+// successive snapshots of a grid, recording does nothing when disabled
+// so that simulations can take a recorder whether they're being debugged or not
+#[derive(Clone, Debug)]
+pub struct Recorder<T> {
+    frames: Vec<Frame<T>>,
+    enabled: bool,
+}
+
+#[allow(unused)]
+impl<T> Recorder<T>
+where
+    T: Clone,
+{
+    pub fn new() -> Self {
+        Self {
+            frames: vec![],
+            enabled: true,
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self {
+            frames: vec![],
+            enabled: false,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn record(&mut self, grid: &Grid<T>) {
+        self.record_with(grid, vec![], String::new());
+    }
+
+    pub fn record_with(&mut self, grid: &Grid<T>, highlights: Vec<(usize, usize)>, label: String) {
+        if self.enabled {
+            self.frames.push(Frame {
+                grid: grid.clone(),
+                highlights,
+                label,
+            });
+        }
+    }
+
+    pub fn frames(&self) -> &[Frame<T>] {
+        &self.frames
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Control {
+    TogglePause,
+    Next,
+    Previous,
+    Faster,
+    Slower,
+    Quit,
+}
+
+impl Control {
+    // commands are read line by line: p(ause), n(ext), b(ack), +, -, q(uit)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim() {
+            "p" | "" => Some(Control::TogglePause),
+            "n" => Some(Control::Next),
+            "b" => Some(Control::Previous),
+            "+" => Some(Control::Faster),
+            "-" => Some(Control::Slower),
+            "q" => Some(Control::Quit),
+            _ => None,
+        }
+    }
+}
+
+// plays recorded frames in the terminal
+pub struct Player {
+    pub delay: Duration,
+    pub highlight: Color,
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Self {
+            delay: Duration::from_millis(100),
+            highlight: Color::Yellow,
+        }
+    }
+}
+
+#[allow(unused)]
+impl Player {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            ..Default::default()
+        }
+    }
+
+    // plays to stdout, taking controls from stdin
+    pub fn play<T, F, S>(&self, rec: &Recorder<T>, cell: F) -> io::Result<()>
+    where
+        T: Clone,
+        F: Fn(&T) -> S,
+        S: Display,
+    {
+        let (tx, rx) = channel();
+        thread::spawn(move || {
+            for line in io::stdin().lock().lines().map_while(Result::ok) {
+                if let Some(c) = Control::parse(&line) {
+                    if tx.send(c).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        let stdout = io::stdout();
+        self.play_with(rec, cell, stdout.lock(), rx)
+    }
+
+    pub fn play_with<T, F, S, W>(
+        &self,
+        rec: &Recorder<T>,
+        cell: F,
+        mut out: W,
+        controls: Receiver<Control>,
+    ) -> io::Result<()>
+    where
+        T: Clone,
+        F: Fn(&T) -> S,
+        S: Display,
+        W: Write,
+    {
+        let frames = rec.frames();
+        let mut delay = self.delay;
+        let mut paused = false;
+        let mut pos = 0;
+
+        // hide cursor while playing
+        write!(out, "\x1b[?25l")?;
+
+        while pos < frames.len() {
+            let frame = &frames[pos];
+            write!(out, "\x1b[2J\x1b[H")?;
+            write!(
+                out,
+                "{}",
+                frame
+                    .grid
+                    .render(&cell)
+                    .highlight(frame.highlights.iter().copied(), self.highlight)
+            )?;
+            writeln!(
+                out,
+                "frame {}/{} {} {}",
+                pos + 1,
+                frames.len(),
+                frame.label,
+                if paused { "[paused]" } else { "" }
+            )?;
+            out.flush()?;
+
+            let control = if paused {
+                // wait for the next command
+                controls.recv().ok().or(Some(Control::Quit))
+            } else {
+                thread::sleep(delay);
+                controls.try_recv().ok()
+            };
+
+            match control {
+                Some(Control::TogglePause) => paused = !paused,
+                Some(Control::Next) => pos += 1,
+                Some(Control::Previous) => pos = pos.saturating_sub(1),
+                Some(Control::Faster) => delay /= 2,
+                Some(Control::Slower) => delay *= 2,
+                Some(Control::Quit) => break,
+                None => pos += 1,
+            }
+        }
+
+        write!(out, "\x1b[?25h")?;
+        out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_recorder_is_empty() {
+        let g = Grid::new_with(2, 2, 0u8);
+        let mut rec = Recorder::disabled();
+        rec.record(&g);
+        assert!(rec.frames().is_empty());
+
+        let mut rec = Recorder::new();
+        rec.record(&g);
+        assert_eq!(rec.frames().len(), 1);
+    }
+
+    #[test]
+    fn play_with_controls() {
+        let mut rec = Recorder::new();
+        for i in 0..3u8 {
+            rec.record(&Grid::new_with(1, 1, i));
+        }
+
+        // pause on the first frame, step back (stays on first), step twice, quit
+        let (tx, rx) = channel();
+        for c in [
+            Control::TogglePause,
+            Control::Previous,
+            Control::Next,
+            Control::Next,
+            Control::Quit,
+        ] {
+            tx.send(c).unwrap();
+        }
+
+        let mut out = vec![];
+        Player::new(Duration::ZERO)
+            .play_with(&rec, |v| v.to_string(), &mut out, rx)
+            .unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert_eq!(out.matches("\x1b[2J").count(), 5);
+        assert!(out.contains("frame 3/3"));
+    }
+}
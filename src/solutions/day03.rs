@@ -1,112 +1,55 @@
+use crate::bits::{least_common_mask, most_common_mask, Binary};
 use crate::solver::{ReadExt, Solver};
 use std::io::Read;
 
 pub struct Problem;
 
 impl Solver for Problem {
-    type Input = Vec<String>;
+    type Input = Report;
     type Output1 = u64;
     type Output2 = u64;
 
     fn parse_input<R: Read>(&self, r: R) -> Self::Input {
-        r.split_lines()
+        let numbers: Vec<Binary> = r.try_lines().expect("invalid binary number");
+        Report {
+            width: numbers.iter().map(|n| n.width).max().unwrap_or_default(),
+            values: numbers.iter().map(|n| n.value).collect(),
+        }
     }
 
     fn solve_first(&self, input: &Self::Input) -> Self::Output1 {
-        let bits = input[0].len();
-        let mut ones = vec![0; bits];
-
-        // count 1's for all input
-        for s in input {
-            for (i, b) in s.chars().enumerate() {
-                if b == '1' {
-                    ones[i] += 1;
-                }
-            }
-        }
-
-        let mut gamma = String::new();
-        let mut epsilon = String::new();
-
-        for n in ones {
-            if n > input.len() / 2 {
-                // we have more 1 than 0
-                gamma.push('1');
-                epsilon.push('0');
-            } else {
-                // more 0 than 1
-                gamma.push('0');
-                epsilon.push('1');
-            }
-        }
-
-        let gamma = u64::from_str_radix(&gamma, 2).unwrap_or_default();
-        let epsilon = u64::from_str_radix(&epsilon, 2).unwrap_or_default();
+        let gamma = most_common_mask(&input.values, input.width);
+        let epsilon = least_common_mask(&input.values, input.width);
 
         gamma * epsilon
     }
 
     fn solve_second(&self, input: &Self::Input) -> Self::Output2 {
-        let bits = input[0].len();
-
-        let mut oxygen = input.clone();
-        for bit in 0..bits {
-            oxygen = filter_by_most_common(&oxygen, bit);
-            if oxygen.len() == 1 {
-                break;
-            }
-        }
-        let oxygen = u64::from_str_radix(&oxygen[0], 2).unwrap_or_default();
-
-        let mut co2 = input.clone();
-        for bit in 0..bits {
-            co2 = filter_by_least_common(&co2, bit);
-            if co2.len() == 1 {
-                break;
-            }
-        }
-        let co2 = u64::from_str_radix(&co2[0], 2).unwrap_or_default();
+        let oxygen = filter_by_bit_criteria(input, true);
+        let co2 = filter_by_bit_criteria(input, false);
 
         oxygen * co2
     }
 }
 
-fn filter_by_most_common(numbers: &[String], bit: usize) -> Vec<String> {
-    let (zeroes, ones) = zeroes_and_ones(numbers, bit);
-    let most_common = if ones >= zeroes { b'1' } else { b'0' };
-
-    filter_by(numbers, bit, most_common)
-}
-
-fn filter_by_least_common(numbers: &[String], bit: usize) -> Vec<String> {
-    let (zeroes, ones) = zeroes_and_ones(numbers, bit);
-    let least_common = if ones >= zeroes { b'0' } else { b'1' };
-
-    filter_by(numbers, bit, least_common)
+pub struct Report {
+    values: Vec<u64>,
+    width: usize,
 }
 
-fn zeroes_and_ones(numbers: &[String], bit: usize) -> (usize, usize) {
-    let mut ones = 0;
-    let mut zeroes = 0;
-
-    // count 1's for all input
-    for s in numbers {
-        if let Some(&c) = s.as_bytes().get(bit) {
-            if c == b'1' {
-                ones += 1;
-            } else {
-                zeroes += 1;
-            }
+// keeps the numbers having the most (or least) common value for each bit, starting from the
+// most significant one, until a single number is left
+fn filter_by_bit_criteria(input: &Report, most_common: bool) -> u64 {
+    let mut numbers = input.values.clone();
+    for bit in (0..input.width).rev() {
+        if numbers.len() <= 1 {
+            break;
         }
-    }
 
-    (zeroes, ones)
-}
+        let ones = numbers.iter().filter(|&&n| n >> bit & 1 == 1).count();
+        let keep_ones = (ones * 2 >= numbers.len()) == most_common;
+        numbers.retain(|&n| (n >> bit & 1 == 1) == keep_ones);
+    }
 
-fn filter_by(numbers: &[String], bit: usize, val: u8) -> Vec<String> {
-    numbers
-        .iter()
-        .filter(|n| n.as_bytes().get(bit).cloned().unwrap_or_default() == val)
-        .cloned()
-        .collect()
+    numbers.first().copied().unwrap_or_default()
 }
@@ -1,5 +1,5 @@
 use crate::grid::Grid;
-use crate::solver::{ReadExt, Solver};
+use crate::solver::{Sections, Solver};
 use std::io::Read;
 use std::num::ParseIntError;
 use std::str::FromStr;
@@ -12,12 +12,11 @@ impl Solver for Problem {
     type Output2 = u64;
 
     fn parse_input<R: Read>(&self, r: R) -> Self::Input {
-        let s: Vec<String> = r.split_groups();
-        let picks = s[0].as_bytes().split_commas();
-        let boards = s[1..]
-            .iter()
-            .flat_map(|s| Grid::from_split_whitespace_reader(s.as_bytes()))
-            .collect();
+        let mut s = Sections::from_reader(r);
+        let picks = s.next_commas().expect("invalid picks");
+        let boards = s
+            .rest_with(|s| Grid::from_split_whitespace_reader(s.as_bytes()))
+            .expect("invalid boards");
 
         Bingo { picks, boards }
     }
@@ -87,29 +86,18 @@ impl FromStr for BingoCell {
 }
 
 fn mark_pick(board: &mut Grid<BingoCell>, n: u8) {
-    if let Some(c) = board.as_slice_mut().iter_mut().find(|c| c.value == n) {
+    if let Some(c) = board.iter_mut().find(|c| c.value == n) {
         c.marked = true;
     }
 }
 
 fn has_bingo(board: &Grid<BingoCell>) -> bool {
-    for row in 0..board.h {
-        if board.iter_row(row).all(|c| c.marked) {
-            return true;
-        }
-    }
-    for col in 0..board.w {
-        if board.iter_col(col).all(|c| c.marked) {
-            return true;
-        }
-    }
-
-    false
+    board.rows().any(|row| row.iter().all(|c| c.marked))
+        || board.cols().any(|mut col| col.all(|c| c.marked))
 }
 
 fn board_score(board: &Grid<BingoCell>) -> u64 {
     board
-        .as_slice()
         .iter()
         .filter(|c| !c.marked)
         .map(|c| c.value as u64)
@@ -1,6 +1,5 @@
 use crate::solver::{ReadExt, Solver};
 use std::io::Read;
-use std::iter::repeat;
 
 pub struct Problem;
 
@@ -57,7 +56,7 @@ fn next_state(pop: &mut Vec<u8>) {
 #[allow(unused)]
 fn predict_until_turn(pop: &[u8], last_turn: usize) -> Vec<usize> {
     // initial lookup-table with all existing fishes
-    let mut numbers = repeat(pop.len()).take(last_turn).collect::<Vec<_>>();
+    let mut numbers = std::iter::repeat_n(pop.len(), last_turn).collect::<Vec<_>>();
 
     // browse initial population to add fishes for the next turn then 7 by 7
     for fish in pop {
@@ -65,7 +64,7 @@ fn predict_until_turn(pop: &[u8], last_turn: usize) -> Vec<usize> {
 
         let mut inc = 0;
         for turn in turn_added..=last_turn {
-            if (turn - turn_added) % 7 == 0 {
+            if (turn - turn_added).is_multiple_of(7) {
                 inc += 1;
             }
             numbers[turn - 1] += inc;
@@ -1,5 +1,4 @@
 use crate::solver::{ReadExt, Solver};
-use std::cmp::{max, min};
 use std::io::Read;
 
 pub struct Problem;
@@ -21,7 +20,6 @@ impl Solver for Problem {
     fn solve_second(&self, input: &Self::Input) -> Self::Output2 {
         let (min, max) = min_max(input);
         (min..=max)
-            .into_iter()
             .map(|pos| cost_for_position2(input, pos))
             .min()
             .unwrap_or_default()
@@ -32,7 +30,7 @@ fn median(a: &[u64]) -> f64 {
     let mut a = a.to_vec();
     a.sort_unstable();
 
-    if a.len() % 2 == 0 {
+    if a.len().is_multiple_of(2) {
         (a[a.len() / 2 - 1] + a[a.len() / 2]) as f64 / 2.0
     } else {
         a[a.len() / 2] as f64
@@ -53,11 +51,7 @@ fn min_max(a: &[u64]) -> (u64, u64) {
 }
 
 fn cost_for_position(n: &[u64], pos: u64) -> u64 {
-    n.iter().map(|&n| abs_diff(n, pos)).sum()
-}
-
-fn abs_diff(a: u64, b: u64) -> u64 {
-    max(a, b) - min(a, b)
+    n.iter().map(|&n| n.abs_diff(pos)).sum()
 }
 
 fn cost_for_position2(n: &[u64], pos: u64) -> u64 {
@@ -65,7 +59,7 @@ fn cost_for_position2(n: &[u64], pos: u64) -> u64 {
 }
 
 fn cost(from: u64, to: u64) -> u64 {
-    let n = abs_diff(from, to);
+    let n = from.abs_diff(to);
     n * (n + 1) / 2
 }
 
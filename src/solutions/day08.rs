@@ -1,4 +1,4 @@
-use crate::solver::{ReadExt, Solver};
+use crate::solver::{scan, ReadExt, ScanError, Solver};
 use std::collections::{BTreeSet, HashMap};
 use std::io::Read;
 use std::str::FromStr;
@@ -11,7 +11,7 @@ impl Solver for Problem {
     type Output2 = usize;
 
     fn parse_input<R: Read>(&self, r: R) -> Self::Input {
-        r.split_lines()
+        r.try_lines().expect("invalid entry")
     }
 
     fn solve_first(&self, input: &Self::Input) -> Self::Output1 {
@@ -25,7 +25,7 @@ impl Solver for Problem {
     }
 
     fn solve_second(&self, input: &Self::Input) -> Self::Output2 {
-        input.iter().flat_map(|e| solve_entry(e)).sum()
+        input.iter().flat_map(solve_entry).sum()
     }
 }
 
@@ -37,24 +37,20 @@ pub struct Entry {
 }
 
 impl FromStr for Entry {
-    type Err = &'static str;
+    type Err = ScanError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut s = s.split(" | ");
-        let patterns = s
-            .next()
-            .ok_or("invalid input")?
-            .split_whitespace()
-            .map(|s| BTreeSet::from_iter(s.as_bytes().iter().cloned()))
-            .collect();
-        let result = s
-            .next()
-            .ok_or("invalid input")?
-            .split_whitespace()
-            .map(|s| BTreeSet::from_iter(s.as_bytes().iter().cloned()))
-            .collect();
-
-        Ok(Self { patterns, result })
+        let (patterns, result) = scan!("{} | {}", s => String, String)?;
+        let words = |s: &str| {
+            s.split_whitespace()
+                .map(|s| BTreeSet::from_iter(s.as_bytes().iter().cloned()))
+                .collect()
+        };
+
+        Ok(Self {
+            patterns: words(&patterns),
+            result: words(&result),
+        })
     }
 }
 
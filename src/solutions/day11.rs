@@ -1,7 +1,10 @@
-use crate::grid::Grid;
+use crate::automaton::Automaton;
+use crate::grid::{Connectivity, Grid};
+use crate::image::Rgb;
+use crate::replay::{Player, Recorder};
 use crate::solver::Solver;
-use std::collections::{HashSet, VecDeque};
 use std::io::Read;
+use std::time::Duration;
 
 pub struct Problem;
 
@@ -16,79 +19,72 @@ impl Solver for Problem {
     }
 
     fn solve_first(&self, input: &Self::Input) -> Self::Output1 {
-        let mut input = input.clone();
-        let mut total = 0;
-        for _ in 0..100 {
-            total += next_turn(&mut input);
-        }
-
-        total
+        count_flashes(input, 100, &mut Recorder::disabled())
     }
 
     fn solve_second(&self, input: &Self::Input) -> Self::Output2 {
-        let mut input = input.clone();
-
-        for i in 1..usize::MAX {
-            let n = next_turn(&mut input);
-            if n == 100 {
-                return i;
-            }
-        }
+        let mut a = Automaton::new(input.clone());
+
+        a.run_until(
+            |a| {
+                next_turn(a);
+            },
+            |g| g.all(|&o| o == 0),
+            usize::MAX,
+        )
+        .expect("octopuses never synchronize")
+    }
 
-        unreachable!()
+    fn debug(&self, input: &Self::Input) {
+        let mut rec = Recorder::new();
+        count_flashes(input, 100, &mut rec);
+
+        // flashing octopuses are white, the others get brighter as their energy grows
+        let palette = (0..10)
+            .map(|e| {
+                if e == 0 {
+                    Rgb::WHITE
+                } else {
+                    Rgb::gray(e * 20)
+                }
+            })
+            .collect::<Vec<_>>();
+        rec.save_gif("day11.gif", &palette, 8, Duration::from_millis(100), |&o| o)
+            .expect("can't write day11.gif");
+
+        Player::default()
+            .play(&rec, |o| o.to_string())
+            .expect("can't play recording");
     }
 }
 
-fn next_turn(g: &mut Grid<u8>) -> usize {
-    // set of flashes that already happened
-    let mut flashed = HashSet::new();
-
-    // queue of points to analyze for possible flashes
-    let mut queue = VecDeque::new();
-
-    // 1. increment all
-    for o in g.as_slice_mut() {
-        *o += 1;
+// total number of flashes during the given number of turns, each turn being recorded with the
+// octopuses which flashed highlighted
+fn count_flashes(input: &Grid<u8>, turns: usize, rec: &mut Recorder<u8>) -> usize {
+    let mut a = Automaton::new(input.clone());
+    // labels are only worth formatting when actually recording
+    if rec.is_enabled() {
+        rec.record_with(a.grid(), vec![], "step 0".to_string());
     }
 
-    // 2. check for flashes
-    for y in 0..g.h {
-        for x in 0..g.w {
-            if let Some(&o) = g.get((x, y)) {
-                if o > 9 {
-                    flashed.insert((x, y));
-                    for c in g.neighbours_coords8((x, y)) {
-                        queue.push_back(c);
-                    }
-                }
-            }
+    let mut total = 0;
+    for step in 1..=turns {
+        let flashed = next_turn(&mut a);
+        total += flashed.len();
+        if rec.is_enabled() {
+            rec.record_with(a.grid(), flashed, format!("step {}", step));
         }
     }
 
-    // 3. rinse and repeat while the queue is not empty
-    while let Some(c) = queue.pop_front() {
-        // if the point has flashed already, ignore it
-        if flashed.contains(&c) {
-            continue;
-        }
-
-        if let Some(o) = g.get_mut(c) {
-            *o += 1;
-            if *o > 9 {
-                flashed.insert(c);
-                for c in g.neighbours_coords8(c) {
-                    queue.push_back(c);
-                }
-            }
-        }
-    }
+    total
+}
 
-    // 4. find all flashed and set them to zero
-    for &c in &flashed {
-        if let Some(o) = g.get_mut(c) {
-            *o = 0;
-        }
-    }
+fn next_turn(a: &mut Automaton<u8>) -> Vec<(usize, usize)> {
+    let flash = |o: &mut u8| {
+        *o += 1;
+        *o > 9
+    };
 
-    flashed.len()
+    // increment all, cascade flashes to the neighbours, and reset all flashed octopuses to zero
+    a.cascade(Connectivity::Eight, flash, flash, |o| *o = 0)
 }
@@ -1,207 +1,143 @@
-use crate::solver::{ReadExt, Solver};
-use std::collections::{HashMap, HashSet, VecDeque};
+use crate::memo::memoize;
+use crate::solver::{scan_pattern, ScanError, ZeroCopySolver};
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Display, Formatter};
 use std::hash::Hash;
-use std::io::Read;
-use std::str::FromStr;
 
 pub struct Problem;
 
-impl Solver for Problem {
-    type Input = Vec<Link>;
+impl ZeroCopySolver for Problem {
+    type Input<'a> = Vec<Link<'a>>;
     type Output1 = usize;
     type Output2 = usize;
 
-    fn parse_input<R: Read>(&self, r: R) -> Self::Input {
-        r.split_lines()
+    fn parse<'a>(&self, s: &'a str) -> Self::Input<'a> {
+        s.lines()
+            .map(Link::parse)
+            .collect::<Result<_, _>>()
+            .expect("invalid link")
     }
 
-    fn solve_first(&self, input: &Self::Input) -> Self::Output1 {
+    fn solve_first(&self, input: &Self::Input<'_>) -> Self::Output1 {
         // build graph
         let g = build_graph(input);
-        let traversals = find_all_traversals(&g);
 
-        traversals.len()
+        count_paths(&g, false)
     }
 
-    fn solve_second(&self, input: &Self::Input) -> Self::Output2 {
+    fn solve_second(&self, input: &Self::Input<'_>) -> Self::Output2 {
         // build graph
         let g = build_graph(input);
-        let traversals = find_all_traversals_part2(&g);
 
-        traversals.len()
+        count_paths(&g, true)
     }
 }
 
-#[allow(unused)]
-fn print_traversal(t: &[Node]) {
-    println!(
-        "{}",
-        t.iter()
-            .map(|n| n.to_string())
-            .collect::<Vec<_>>()
-            .join(",")
-    );
-}
-
-#[derive(Clone)]
-struct Traversal {
-    nodes: Vec<Node>,
-    small_visited: HashSet<Node>,
-}
-
-fn find_all_traversals(g: &Graph<Node>) -> Vec<Vec<Node>> {
-    // queue of nodes to visit
-    let mut to_visit = VecDeque::new();
-    // final traversals
-    let mut traversals = vec![];
-
-    // follow the graph
-    to_visit.push_back(Traversal {
-        nodes: vec![Node::Start],
-        small_visited: HashSet::new(),
-    });
-    while let Some(node_traversal) = to_visit.pop_front() {
-        if let Some(last) = node_traversal.nodes.last() {
-            // if we're at the end, add to the traversals, and continue
-            if last == &Node::End {
-                traversals.push(node_traversal.nodes);
-                continue;
-            }
-
-            // find all possible subsequent nodes, and add those to the queue
-            if let Some(next) = g.nodes.get(last) {
-                for node in next {
-                    // ignore small nodes if we've seen them before
-                    if node_traversal.small_visited.contains(node) {
-                        continue;
-                    }
-
-                    let mut v = node_traversal.clone();
-                    v.nodes.push(node.clone());
-                    if matches!(node, Node::Small(_)) {
-                        v.small_visited.insert(node.clone());
-                    }
-
-                    to_visit.push_back(v);
-                }
+// number of paths from start to end visiting small caves at most once, except for a single
+// one which can be visited twice when `allow_twice` is set
+fn count_paths<'a>(g: &Graph<Node<'a>>, allow_twice: bool) -> usize {
+    // small caves visited so far are kept as a bit mask
+    let small_bits: HashMap<Node<'a>, u64> = g
+        .nodes
+        .keys()
+        .filter(|n| matches!(n, Node::Small(_)))
+        .enumerate()
+        .map(|(i, &n)| {
+            let bit = u32::try_from(i).ok().and_then(|i| 1u64.checked_shl(i));
+            (n, bit.expect("too many small caves"))
+        })
+        .collect();
+
+    let mut paths = memoize(
+        |paths: &mut dyn FnMut((Node<'a>, u64, bool)) -> usize, (node, visited, twice)| {
+            if node == Node::End {
+                return 1;
             }
-        }
-    }
-
-    traversals
-}
-
-#[derive(Clone)]
-struct TraversalPart2 {
-    nodes: Vec<Node>,
-    small_visited: HashMap<Node, usize>,
-    small_dup: Option<Node>,
-}
 
-fn find_all_traversals_part2(g: &Graph<Node>) -> Vec<Vec<Node>> {
-    // queue of nodes to visit
-    let mut to_visit = VecDeque::new();
-    // final traversals
-    let mut traversals = vec![];
-
-    // follow the graph
-    to_visit.push_back(TraversalPart2 {
-        nodes: vec![Node::Start],
-        small_visited: HashMap::new(),
-        small_dup: None,
-    });
-    while let Some(node_traversal) = to_visit.pop_front() {
-        if let Some(last) = node_traversal.nodes.last() {
-            // if we're at the end, add to the traversals, and continue
-            if last == &Node::End {
-                traversals.push(node_traversal.nodes);
-                continue;
-            }
-
-            // find all possible subsequent nodes, and add those to the queue
-            if let Some(next) = g.nodes.get(last) {
-                for node in next {
-                    // ignore small nodes if we've seen them twice before
-                    if let Some(dup) = &node_traversal.small_dup {
-                        // if we find the 3rd passage of one node, bail
-                        // or if we have dupes and we try to dupe another one, bail
-                        if dup == node || node_traversal.small_visited.contains_key(node) {
-                            continue;
-                        }
+            let next = match g.nodes.get(&node) {
+                Some(next) => next,
+                None => return 0,
+            };
+            next.iter()
+                .map(|&n| {
+                    let bit = small_bits.get(&n).copied().unwrap_or_default();
+                    if visited & bit == 0 {
+                        paths((n, visited | bit, twice))
+                    } else if allow_twice && !twice {
+                        paths((n, visited, true))
+                    } else {
+                        0
                     }
+                })
+                .sum()
+        },
+    );
 
-                    let mut v = node_traversal.clone();
-                    v.nodes.push(node.clone());
-                    if matches!(node, Node::Small(_)) {
-                        let e = v.small_visited.entry(node.clone()).or_default();
-                        *e += 1;
-                        if *e == 2 {
-                            v.small_dup = Some(node.clone());
-                        }
-                    }
-                    to_visit.push_back(v);
-                }
-            }
-        }
-    }
-
-    traversals
+    paths.call((Node::Start, 0, false))
 }
 
-fn build_graph(links: &[Link]) -> Graph<Node> {
+fn build_graph<'a>(links: &[Link<'a>]) -> Graph<Node<'a>> {
     let mut g = Graph::new();
 
     for link in links {
-        let from = link.from.parse::<Node>().ok();
-        let to = link.to.parse::<Node>().ok();
+        let from = Node::from_name(link.from);
+        let to = Node::from_name(link.to);
 
-        if let (Some(from), Some(to)) = (from, to) {
-            if from != Node::End && to != Node::Start {
-                g.add(from.clone(), to.clone());
-            }
+        if from != Node::End && to != Node::Start {
+            g.add(from, to);
+        }
 
-            if from != Node::Start && to != Node::End {
-                g.add(to.clone(), from.clone());
-            }
+        if from != Node::Start && to != Node::End {
+            g.add(to, from);
         }
     }
 
     g
 }
 
-pub struct Link {
-    from: String,
-    to: String,
+pub struct Link<'a> {
+    from: &'a str,
+    to: &'a str,
 }
 
-impl FromStr for Link {
-    type Err = &'static str;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut s = s.split('-');
-        let from = s.next().ok_or("missing delimiter")?.to_string();
-        let to = s.next().ok_or("missing delimiter")?.to_string();
-
-        Ok(Self { from, to })
+impl<'a> Link<'a> {
+    fn parse(s: &'a str) -> Result<Self, ScanError> {
+        match scan_pattern("{}-{}", s)?[..] {
+            [from, to] => Ok(Self {
+                from: from.text,
+                to: to.text,
+            }),
+            _ => unreachable!(),
+        }
     }
 }
 
-#[derive(Clone, Eq, PartialEq, Hash)]
-enum Node {
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+enum Node<'a> {
     Start,
     End,
-    Small(String),
-    Big(String),
+    Small(&'a str),
+    Big(&'a str),
 }
 
-impl Debug for Node {
+impl<'a> Node<'a> {
+    fn from_name(s: &'a str) -> Self {
+        match s {
+            "start" => Node::Start,
+            "end" => Node::End,
+            s if s.starts_with(|c: char| c.is_ascii_uppercase()) => Node::Big(s),
+            s => Node::Small(s),
+        }
+    }
+}
+
+impl Debug for Node<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self)
     }
 }
 
-impl Display for Node {
+impl Display for Node<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
@@ -209,28 +145,13 @@ impl Display for Node {
             match self {
                 Node::Start => "start",
                 Node::End => "end",
-                Node::Small(s) => s.as_str(),
-                Node::Big(s) => s.as_str(),
+                Node::Small(s) => s,
+                Node::Big(s) => s,
             }
         )
     }
 }
 
-impl FromStr for Node {
-    type Err = &'static str;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "start" => Ok(Node::Start),
-            "end" => Ok(Node::End),
-            s if s.chars().next().ok_or("empty string")?.is_ascii_uppercase() => {
-                Ok(Node::Big(s.to_string()))
-            }
-            s => Ok(Node::Small(s.to_string())),
-        }
-    }
-}
-
 #[derive(Debug)]
 struct Graph<T> {
     nodes: HashMap<T, HashSet<T>>,
@@ -249,7 +170,7 @@ where
     T: Eq + Hash,
 {
     fn add(&mut self, from: T, to: T) {
-        let e = self.nodes.entry(from).or_insert_with(HashSet::new);
+        let e = self.nodes.entry(from).or_default();
         e.insert(to);
     }
 }
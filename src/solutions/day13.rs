@@ -1,21 +1,25 @@
-use crate::grid::{Grid, GridPoint};
-use crate::solver::{ReadExt, Solver};
-use std::cmp::{max, min};
-use std::fmt::{Display, Formatter};
+use crate::bitgrid::BitGrid;
+use crate::grid::GridPoint;
+use crate::image::Rgb;
+use crate::ocr;
+use crate::replay::{Player, Recorder};
+use crate::solver::{IntsExt, Sections, Solver};
+use std::error::Error;
 use std::io::Read;
 use std::str::FromStr;
+use std::time::Duration;
 
 pub struct Problem;
 
 impl Solver for Problem {
     type Input = Instr;
     type Output1 = usize;
-    type Output2 = &'static str;
+    type Output2 = String;
 
     fn parse_input<R: Read>(&self, r: R) -> Self::Input {
-        let g: Vec<String> = r.split_groups();
-        let points = g[0].as_bytes().split_lines();
-        let folds = g[1].as_bytes().split_lines();
+        let mut s = Sections::from_reader(r);
+        let points = s.next_lines().expect("invalid points");
+        let folds = s.next_lines().expect("invalid folds");
 
         Instr { points, folds }
     }
@@ -24,118 +28,71 @@ impl Solver for Problem {
         let g = make_grid(&input.points);
         let f = input.folds.first().expect("non empty folds");
 
-        let g = fold_grid(&g, f);
-
-        g.as_slice()
-            .iter()
-            .filter(|p| matches!(p, Paper::Dot))
-            .count()
+        fold_grid(&g, f).count_ones()
     }
 
     fn solve_second(&self, input: &Self::Input) -> Self::Output2 {
-        let mut g = make_grid(&input.points);
-        for f in &input.folds {
-            g = fold_grid(&g, f);
-        }
-
-        println!("{}", g);
-
-        // output without OCR :)
-        "HECRZKPR"
-    }
-}
+        let g = fold_all(input, &mut Recorder::disabled());
 
-fn make_grid(dots: &[GridPoint]) -> Grid<Paper> {
-    let mut w = 0;
-    let mut h = 0;
-    for d in dots {
-        if d.x + 1 > w {
-            w = d.x + 1;
-        }
-        if d.y + 1 > h {
-            h = d.y + 1;
-        }
+        // shapes which aren't letters (like the example's square) are shown as they are, after
+        // the reason why they couldn't be read
+        ocr::recognize(&g.to_grid(), |&b| b).unwrap_or_else(|e| format!("{}\n{}", e, g))
     }
 
-    let mut g = Grid::new_with(w, h, Paper::Empty);
-    for d in dots {
-        if let Some(p) = g.get_mut(d) {
-            *p = Paper::Dot;
-        }
-    }
+    fn debug(&self, input: &Self::Input) {
+        let mut rec = Recorder::new();
+        fold_all(input, &mut rec);
 
-    g
-}
+        rec.save_gif(
+            "day13.gif",
+            &[Rgb::BLACK, Rgb::WHITE],
+            4,
+            Duration::from_secs(1),
+            |&dot| dot as u8,
+        )
+        .expect("can't write day13.gif");
 
-fn fold_grid(g: &Grid<Paper>, f: &Fold) -> Grid<Paper> {
-    match *f {
-        Fold::X(x) => fold_grid_x(g, x),
-        Fold::Y(y) => fold_grid_y(g, y),
+        Player::new(Duration::from_secs(1))
+            .play(&rec, |&dot| if dot { "#" } else { "." }.to_string())
+            .expect("can't play recording");
     }
 }
 
-fn fold_grid_x(g: &Grid<Paper>, fold_x: usize) -> Grid<Paper> {
-    let mut new_grid = Grid::new_with(fold_x, g.h, Paper::Empty);
-
-    // copy old points
-    for y in 0..g.h {
-        for x in 0..fold_x {
-            let p = g.get((x, y)).unwrap_or(&Paper::Empty);
-            if let Some(np) = new_grid.get_mut((x, y)) {
-                if matches!(p, Paper::Dot) {
-                    *np = Paper::Dot;
-                }
-            }
-        }
-    }
+fn make_grid(dots: &[GridPoint]) -> BitGrid {
+    let w = dots.iter().map(|d| d.x + 1).max().unwrap_or_default();
+    let h = dots.iter().map(|d| d.y + 1).max().unwrap_or_default();
 
-    // copy folded points
-    for y in 0..g.h {
-        for x in (fold_x + 1)..g.w {
-            let new_x = fold_x - abs_diff(fold_x, x);
-            if matches!(g.get((x, y)), Some(Paper::Dot)) {
-                if let Some(np) = new_grid.get_mut((new_x, y)) {
-                    *np = Paper::Dot;
-                }
-            }
-        }
+    let mut g = BitGrid::new(w, h);
+    for d in dots {
+        g.set(d, true);
     }
 
-    new_grid
+    g
 }
 
-fn fold_grid_y(g: &Grid<Paper>, fold_y: usize) -> Grid<Paper> {
-    let mut new_grid = Grid::new_with(g.w, fold_y, Paper::Empty);
-
-    // copy old points
-    for y in 0..fold_y {
-        for x in 0..g.w {
-            let p = g.get((x, y)).unwrap_or(&Paper::Empty);
-            if let Some(np) = new_grid.get_mut((x, y)) {
-                if matches!(p, Paper::Dot) {
-                    *np = Paper::Dot;
-                }
-            }
-        }
+// applies all the folds, recording the paper after each of them
+fn fold_all(input: &Instr, rec: &mut Recorder<bool>) -> BitGrid {
+    let mut g = make_grid(&input.points);
+    // unpacking the bits is only worth it when actually recording
+    if rec.is_enabled() {
+        rec.record_with(&g.to_grid(), vec![], "unfolded".to_string());
     }
 
-    // copy folded points
-    for y in (fold_y + 1)..g.h {
-        for x in 0..g.w {
-            let new_y = fold_y - abs_diff(fold_y, y);
-            if matches!(g.get((x, y)), Some(Paper::Dot)) {
-                if let Some(np) = new_grid.get_mut((x, new_y)) {
-                    *np = Paper::Dot;
-                }
-            }
+    for f in &input.folds {
+        g = fold_grid(&g, f);
+        if rec.is_enabled() {
+            rec.record_with(&g.to_grid(), vec![], format!("{:?}", f));
         }
     }
 
-    new_grid
+    g
 }
 
-fn abs_diff(a: usize, b: usize) -> usize {
-    max(a, b) - min(a, b)
+fn fold_grid(g: &BitGrid, f: &Fold) -> BitGrid {
+    match *f {
+        Fold::X(x) => g.fold_left(x),
+        Fold::Y(y) => g.fold_up(y),
+    }
 }
 
 #[derive(Debug)]
@@ -145,19 +102,13 @@ pub struct Instr {
 }
 
 impl FromStr for GridPoint {
-    type Err = &'static str;
+    type Err = Box<dyn Error>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut s = s.split(',');
+        let [x, y] = s.ints_n()?;
         Ok(Self {
-            x: s.next()
-                .ok_or("invalid string")?
-                .parse()
-                .map_err(|_| "invalid number")?,
-            y: s.next()
-                .ok_or("invalid string")?
-                .parse()
-                .map_err(|_| "invalid number")?,
+            x: x.try_into()?,
+            y: y.try_into()?,
         })
     }
 }
@@ -169,40 +120,15 @@ pub enum Fold {
 }
 
 impl FromStr for Fold {
-    type Err = &'static str;
+    type Err = Box<dyn Error>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = &s[11..];
-        let mut s = s.split('=');
-        let axis = s.next().ok_or("invalid string")?;
-        let val = s
-            .next()
-            .ok_or("invalid string")?
-            .parse()
-            .map_err(|_| "invalid number")?;
-        match axis {
-            "x" => Ok(Fold::X(val)),
-            "y" => Ok(Fold::Y(val)),
-            _ => Err("invalid axis"),
+        let axis = s.strip_prefix("fold along ").and_then(|s| s.chars().next());
+        let [at] = s.ints_n()?;
+        match (axis, at.try_into()?) {
+            (Some('x'), at) => Ok(Fold::X(at)),
+            (Some('y'), at) => Ok(Fold::Y(at)),
+            _ => Err(format!("invalid fold {:?}", s).into()),
         }
     }
 }
-
-#[derive(Clone)]
-enum Paper {
-    Empty,
-    Dot,
-}
-
-impl Display for Paper {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Paper::Empty => ".",
-                Paper::Dot => "#",
-            }
-        )
-    }
-}
@@ -1,4 +1,4 @@
-use crate::solver::{ReadExt, Solver};
+use crate::solver::{scan, Sections, Solver};
 use std::collections::HashMap;
 use std::io::Read;
 
@@ -82,16 +82,22 @@ pub struct Instr {
 
 impl Instr {
     fn from_reader<R: Read>(r: R) -> Self {
-        let s: Vec<String> = r.split_groups();
-        let template = s[0].chars().collect();
-        let rules_str: Vec<String> = s[1].as_bytes().split_lines();
+        let mut s = Sections::from_reader(r);
+        let template = s
+            .next_section::<String>()
+            .expect("invalid template")
+            .chars()
+            .collect();
+        let rules_str: Vec<String> = s.next_lines().expect("invalid rules");
 
         let mut rules = HashMap::new();
         for r in &rules_str {
-            let chars = r.chars().collect::<Vec<_>>();
-            let key = chars[0..2].to_vec();
-            let val = chars[6];
-            rules.insert(key, (vec![chars[0], val], vec![val, chars[1]]));
+            let (pair, val) = scan!("{} -> {}", r => String, char).expect("invalid rule");
+            let (a, b) = match pair.chars().collect::<Vec<_>>()[..] {
+                [a, b] => (a, b),
+                _ => panic!("invalid pair {:?}", pair),
+            };
+            rules.insert(vec![a, b], (vec![a, val], vec![val, b]));
         }
 
         Self { template, rules }
@@ -1,5 +1,6 @@
 // DO NOT EDIT THIS FILE
-use crate::solver::Solver;
+#[allow(unused_imports)]
+use crate::solver::{Solver, ZeroCopySolver};
 
 mod day01;
 mod day02;
@@ -16,22 +17,22 @@ mod day12;
 mod day13;
 mod day14;
 
-pub fn exec_day(day: i32) {
+pub fn exec_day(day: i32, debug: bool) {
     match day {
-        1 => day01::Problem {}.solve(day),
-        2 => day02::Problem {}.solve(day),
-        3 => day03::Problem {}.solve(day),
-        4 => day04::Problem {}.solve(day),
-        5 => day05::Problem {}.solve(day),
-        6 => day06::Problem {}.solve(day),
-        7 => day07::Problem {}.solve(day),
-        8 => day08::Problem {}.solve(day),
-        9 => day09::Problem {}.solve(day),
-        10 => day10::Problem {}.solve(day),
-        11 => day11::Problem {}.solve(day),
-        12 => day12::Problem {}.solve(day),
-        13 => day13::Problem {}.solve(day),
-        14 => day14::Problem {}.solve(day),
+        1 => day01::Problem {}.solve(day, debug),
+        2 => day02::Problem {}.solve(day, debug),
+        3 => day03::Problem {}.solve(day, debug),
+        4 => day04::Problem {}.solve(day, debug),
+        5 => day05::Problem {}.solve(day, debug),
+        6 => day06::Problem {}.solve(day, debug),
+        7 => day07::Problem {}.solve(day, debug),
+        8 => day08::Problem {}.solve(day, debug),
+        9 => day09::Problem {}.solve(day, debug),
+        10 => day10::Problem {}.solve(day, debug),
+        11 => day11::Problem {}.solve(day, debug),
+        12 => day12::Problem {}.solve(day, debug),
+        13 => day13::Problem {}.solve(day, debug),
+        14 => day14::Problem {}.solve(day, debug),
         d => println!("Day {} hasn't been solved yet :(", d),
     }
 }
@@ -1,10 +1,10 @@
-use std::fmt::Display;
-use std::fs::File;
+use crate::input;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
 use std::io;
-use std::io::{BufRead, BufReader, Read};
-use std::path::Path;
+use std::io::{BufRead, BufReader, Lines, Read};
 use std::str::FromStr;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 fn input_file(day: i32) -> String {
     format!("input/day{:02}", day)
@@ -19,64 +19,566 @@ pub trait Solver {
     fn solve_first(&self, input: &Self::Input) -> Self::Output1;
     fn solve_second(&self, input: &Self::Input) -> Self::Output2;
 
-    fn load_input<P: AsRef<Path>>(&self, p: P) -> io::Result<Self::Input> {
-        let f = File::open(p)?;
-        Ok(self.parse_input(f))
+    // parses input bytes already in memory
+    fn parse_bytes(&self, b: &[u8]) -> Self::Input {
+        self.parse_input(io::Cursor::new(b))
     }
 
-    fn solve(&self, day: i32) {
-        let input_file = input_file(day);
-        let input = self
-            .load_input(input_file)
-            .expect("unable to open input file");
+    // called after solving when running in debug mode, for visualizations and such
+    fn debug(&self, _input: &Self::Input) {
+        println!("No debug output for this day");
+    }
 
-        let start = Instant::now();
-        let s1 = self.solve_first(&input);
-        let time = start.elapsed();
-        println!("Solution 1: {:<20} ({}ms)", s1, time.as_secs_f64() * 1000.0);
+    fn solve(&self, day: i32, debug: bool) {
+        let bytes = input::load(input_file(day)).expect("unable to open input file");
 
-        let start = Instant::now();
-        let s2 = self.solve_second(&input);
-        let time = start.elapsed();
-        println!("Solution 2: {:<20} ({}ms)", s2, time.as_secs_f64() * 1000.0);
+        run(
+            bytes.len(),
+            || self.parse_bytes(&bytes),
+            |input| self.solve_first(input),
+            |input| self.solve_second(input),
+            debug.then_some(|input: &Self::Input| self.debug(input)),
+        );
     }
 }
 
-pub trait ReadExt<T> {
-    fn split_commas(self) -> Vec<T>;
-    fn split_lines(self) -> Vec<T>;
-    fn split_groups(self) -> Vec<T>;
+// alternative to Solver for days whose parsed input borrows from the raw input text,
+// so that parsing doesn't have to allocate
+pub trait ZeroCopySolver {
+    type Input<'a>;
+    type Output1: Display;
+    type Output2: Display;
+
+    fn parse<'a>(&self, s: &'a str) -> Self::Input<'a>;
+    fn solve_first(&self, input: &Self::Input<'_>) -> Self::Output1;
+    fn solve_second(&self, input: &Self::Input<'_>) -> Self::Output2;
+
+    fn debug(&self, _input: &Self::Input<'_>) {
+        println!("No debug output for this day");
+    }
+
+    fn solve(&self, day: i32, debug: bool) {
+        let bytes = input::load(input_file(day)).expect("unable to open input file");
+        let s = std::str::from_utf8(&bytes).expect("input isn't valid UTF-8");
+
+        run(
+            s.len(),
+            || self.parse(s),
+            |input| self.solve_first(input),
+            |input| self.solve_second(input),
+            debug.then_some(|input: &Self::Input<'_>| self.debug(input)),
+        );
+    }
 }
 
-impl<R, T> ReadExt<T> for R
-where
-    R: Read,
-    T: FromStr,
+// parses and solves both parts, timing each step separately, then runs the debug output
+fn run<I, O1, O2>(
+    len: usize,
+    parse: impl FnOnce() -> I,
+    first: impl FnOnce(&I) -> O1,
+    second: impl FnOnce(&I) -> O2,
+    debug: Option<impl FnOnce(&I)>,
+) where
+    O1: Display,
+    O2: Display,
 {
-    fn split_commas(self) -> Vec<T> {
+    let (input, time) = timed(parse);
+    report("Parsing:", format!("{} bytes", len), time);
+
+    let (s1, time) = timed(|| first(&input));
+    report("Solution 1:", s1, time);
+
+    let (s2, time) = timed(|| second(&input));
+    report("Solution 2:", s2, time);
+
+    if let Some(debug) = debug {
+        debug(&input);
+    }
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let v = f();
+    (v, start.elapsed())
+}
+
+fn report(label: &str, value: impl Display, time: Duration) {
+    println!(
+        "{:<12}{:<20} ({}ms)",
+        label,
+        value,
+        time.as_secs_f64() * 1000.0
+    );
+}
+
+pub trait ReadExt {
+    fn commas_iter<T: FromStr>(self) -> impl Iterator<Item = T>;
+    fn lines_iter<T: FromStr>(self) -> impl Iterator<Item = T>;
+    fn groups_iter<T: FromStr>(self) -> impl Iterator<Item = T>;
+
+    fn split_commas<T: FromStr>(self) -> Vec<T>;
+    fn split_lines<T: FromStr>(self) -> Vec<T>;
+    fn split_groups<T: FromStr>(self) -> Vec<T>;
+
+    // one value per line, failing on the first line which can't be parsed
+    fn try_lines<T>(self) -> Result<Vec<T>, String>
+    where
+        T: FromStr,
+        T::Err: Display;
+
+    // all the integers of a whole input, see `IntsExt`
+    #[allow(unused)]
+    fn read_ints(self) -> Result<Vec<i64>, String>;
+}
+
+impl<R: Read> ReadExt for R {
+    fn commas_iter<T: FromStr>(self) -> impl Iterator<Item = T> {
         BufReader::new(self)
             .split(b',')
-            .flatten()
+            .map_while(Result::ok)
             .flat_map(String::from_utf8)
-            .flat_map(|s| s.parse())
-            .collect()
+            .flat_map(|s| s.trim().parse())
     }
 
-    fn split_lines(self) -> Vec<T> {
+    fn lines_iter<T: FromStr>(self) -> impl Iterator<Item = T> {
         BufReader::new(self)
             .lines()
-            .flatten()
+            .map_while(Result::ok)
             .flat_map(|l| l.parse())
-            .collect()
     }
 
-    fn split_groups(self) -> Vec<T> {
-        BufReader::new(self)
-            .lines()
-            .flatten()
-            .collect::<Vec<_>>()
-            .split(|l| l.is_empty())
-            .flat_map(|e| e.join("\n").parse())
-            .collect::<Vec<T>>()
+    fn groups_iter<T: FromStr>(self) -> impl Iterator<Item = T> {
+        Groups {
+            lines: BufReader::new(self).lines(),
+            done: false,
+        }
+        .flat_map(|g| g.parse())
+    }
+
+    fn split_commas<T: FromStr>(self) -> Vec<T> {
+        self.commas_iter().collect()
+    }
+
+    fn split_lines<T: FromStr>(self) -> Vec<T> {
+        self.lines_iter().collect()
+    }
+
+    fn split_groups<T: FromStr>(self) -> Vec<T> {
+        self.groups_iter().collect()
+    }
+
+    fn try_lines<T>(mut self) -> Result<Vec<T>, String>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let mut s = String::new();
+        self.read_to_string(&mut s).map_err(|e| e.to_string())?;
+        parse_all(s.lines(), "line")
+    }
+
+    fn read_ints(mut self) -> Result<Vec<i64>, String> {
+        let mut s = String::new();
+        self.read_to_string(&mut s).map_err(|e| e.to_string())?;
+        s.ints().map_err(|e| e.to_string())
+    }
+}
+
+// blocks of lines separated by empty lines, joined back with newlines
+struct Groups<R> {
+    lines: Lines<BufReader<R>>,
+    done: bool,
+}
+
+impl<R: Read> Iterator for Groups<R> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let mut group = vec![];
+        loop {
+            match self.lines.next() {
+                Some(Ok(l)) if l.is_empty() => break,
+                Some(Ok(l)) => group.push(l),
+                _ => {
+                    self.done = true;
+                    break;
+                }
+            }
+        }
+        Some(group.join("\n"))
+    }
+}
+
+// error when reading a section of a multi-part input, `index` counting from 0
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SectionError {
+    Missing(usize),
+    Invalid { index: usize, msg: String },
+}
+
+impl Display for SectionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SectionError::Missing(index) => write!(f, "missing section {}", index),
+            SectionError::Invalid { index, msg } => write!(f, "invalid section {}: {}", index, msg),
+        }
+    }
+}
+
+impl Error for SectionError {}
+
+// input made of several parts separated by blank lines, read one typed section after the other
+#[derive(Clone, Debug)]
+pub struct Sections {
+    sections: Vec<String>,
+    next: usize,
+}
+
+#[allow(unused)]
+impl Sections {
+    pub fn from_reader<R: Read>(r: R) -> Self {
+        Self {
+            sections: r.split_groups(),
+            next: 0,
+        }
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.sections.len() - self.next
+    }
+
+    pub fn next_with<T, E, F>(&mut self, f: F) -> Result<T, SectionError>
+    where
+        E: Display,
+        F: FnOnce(&str) -> Result<T, E>,
+    {
+        let index = self.next;
+        let s = self
+            .sections
+            .get(index)
+            .ok_or(SectionError::Missing(index))?;
+        self.next += 1;
+        f(s).map_err(|e| SectionError::Invalid {
+            index,
+            msg: e.to_string(),
+        })
+    }
+
+    pub fn next_section<T>(&mut self) -> Result<T, SectionError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.next_with(str::parse)
+    }
+
+    // one value per line of the next section
+    pub fn next_lines<T>(&mut self) -> Result<Vec<T>, SectionError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.next_with(|s| parse_all(s.lines(), "line"))
+    }
+
+    // comma separated values of the next section
+    pub fn next_commas<T>(&mut self) -> Result<Vec<T>, SectionError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.next_with(|s| parse_all(s.trim().split(','), "value"))
+    }
+
+    // all the remaining sections, each one converted by the given function
+    pub fn rest_with<T, E, F>(&mut self, mut f: F) -> Result<Vec<T>, SectionError>
+    where
+        E: Display,
+        F: FnMut(&str) -> Result<T, E>,
+    {
+        let mut values = Vec::with_capacity(self.remaining());
+        while self.remaining() > 0 {
+            values.push(self.next_with(&mut f)?);
+        }
+        Ok(values)
+    }
+
+    // all the remaining sections, each one parsed as an item of the collection
+    pub fn rest_as<C>(&mut self) -> Result<C, SectionError>
+    where
+        C: IntoIterator + FromIterator<C::Item>,
+        C::Item: FromStr,
+        <C::Item as FromStr>::Err: Display,
+    {
+        Ok(self.rest_with(str::parse)?.into_iter().collect())
+    }
+}
+
+fn parse_all<'a, T, I>(items: I, what: &str) -> Result<Vec<T>, String>
+where
+    T: FromStr,
+    T::Err: Display,
+    I: Iterator<Item = &'a str>,
+{
+    items
+        .enumerate()
+        .map(|(i, s)| {
+            s.parse()
+                .map_err(|e| format!("{} {} ({:?}): {}", what, i, s, e))
+        })
+        .collect()
+}
+
+// error from matching a string against a scan pattern, `pos` being the byte offset in the
+// input where matching failed
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScanError {
+    pub input: String,
+    pub pos: usize,
+    pub msg: String,
+}
+
+impl ScanError {
+    fn new(input: &str, pos: usize, msg: String) -> Self {
+        Self {
+            input: input.to_string(),
+            pos,
+            msg,
+        }
+    }
+}
+
+impl Display for ScanError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at position {} in {:?}",
+            self.msg, self.pos, self.input
+        )
+    }
+}
+
+impl Error for ScanError {}
+
+// part of the input matched by a `{}` placeholder, along with its offset
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Field<'a> {
+    pub input: &'a str,
+    pub pos: usize,
+    pub text: &'a str,
+}
+
+impl Field<'_> {
+    pub fn parse<T>(&self) -> Result<T, ScanError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.text.parse().map_err(|e| {
+            ScanError::new(
+                self.input,
+                self.pos,
+                format!("invalid field {:?}: {}", self.text, e),
+            )
+        })
+    }
+}
+
+// matches the input against a pattern made of literal text and `{}` placeholders, each
+// placeholder matching everything up to the next literal (or the end of the input for the
+// last one); two placeholders can't follow each other since the split would be ambiguous
+pub fn scan_pattern<'a>(pattern: &str, s: &'a str) -> Result<Vec<Field<'a>>, ScanError> {
+    let mut parts = pattern.split("{}").peekable();
+    let prefix = parts.next().unwrap_or_default();
+    if !s.starts_with(prefix) {
+        return Err(ScanError::new(s, 0, format!("expected {:?}", prefix)));
+    }
+
+    let mut pos = prefix.len();
+    let mut fields = vec![];
+    while let Some(lit) = parts.next() {
+        let rest = &s[pos..];
+        let len = if parts.peek().is_none() {
+            rest.strip_suffix(lit)
+                .ok_or_else(|| ScanError::new(s, s.len(), format!("expected {:?}", lit)))?
+                .len()
+        } else {
+            assert!(!lit.is_empty(), "adjacent placeholders in {:?}", pattern);
+            rest.find(lit)
+                .ok_or_else(|| ScanError::new(s, pos, format!("expected {:?}", lit)))?
+        };
+        if len == 0 {
+            return Err(ScanError::new(s, pos, "empty field".to_string()));
+        }
+
+        fields.push(Field {
+            input: s,
+            pos,
+            text: &rest[..len],
+        });
+        pos += len + lit.len();
+    }
+
+    Ok(fields)
+}
+
+// scans the input with the pattern and parses each field into the given types, evaluating
+// to a `Result` of the tuple of fields, e.g. `scan!("{},{}", s => i64, i64)`
+macro_rules! scan {
+    ($pattern:expr, $s:expr => $($t:ty),+ $(,)?) => {{
+        let pattern: &str = $pattern;
+        $crate::solver::scan_pattern(pattern, $s).and_then(|fields| {
+            let types = [$(stringify!($t)),+];
+            assert_eq!(fields.len(), types.len(), "field count mismatch in {:?}", pattern);
+            let mut fields = fields.iter();
+            Ok(($(fields.next().unwrap().parse::<$t>()?,)+))
+        })
+    }};
+}
+
+pub(crate) use scan;
+
+// extraction of all the signed integers embedded in free-form text, a '-' being a sign
+// unless it follows a digit, so that `y=-10..-5` gives -10 and -5 but `3-4` gives 3 and 4
+pub trait IntsExt {
+    fn ints(&self) -> Result<Vec<i64>, ScanError>;
+    fn ints_n<const N: usize>(&self) -> Result<[i64; N], ScanError>;
+}
+
+impl IntsExt for str {
+    fn ints(&self) -> Result<Vec<i64>, ScanError> {
+        let b = self.as_bytes();
+        let mut ints = vec![];
+        let mut i = 0;
+        while i < b.len() {
+            if !b[i].is_ascii_digit() {
+                i += 1;
+                continue;
+            }
+
+            let negative = i > 0 && b[i - 1] == b'-' && (i < 2 || !b[i - 2].is_ascii_digit());
+            let start = if negative { i - 1 } else { i };
+            while i < b.len() && b[i].is_ascii_digit() {
+                i += 1;
+            }
+            let int = self[start..i]
+                .parse()
+                .map_err(|e| ScanError::new(self, start, format!("invalid integer: {}", e)))?;
+            ints.push(int);
+        }
+        Ok(ints)
+    }
+
+    fn ints_n<const N: usize>(&self) -> Result<[i64; N], ScanError> {
+        let ints = self.ints()?;
+        ints.try_into().map_err(|ints: Vec<i64>| {
+            ScanError::new(
+                self,
+                0,
+                format!("expected {} integers, found {}", N, ints.len()),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_fields() {
+        let r = scan!("{},{} -> {},{}", "0,9 -> 5,9" => i64, i64, i64, i64);
+        assert_eq!(r, Ok((0, 9, 5, 9)));
+
+        let r = scan!("{} -> {}", "CH -> B" => String, char);
+        assert_eq!(r, Ok(("CH".to_string(), 'B')));
+
+        let r = scan!("<{}>", "<abc>" => String);
+        assert_eq!(r, Ok(("abc".to_string(),)));
+
+        let fields = scan_pattern("{}-{}", "start-A").unwrap();
+        let fields = fields.iter().map(|f| (f.pos, f.text)).collect::<Vec<_>>();
+        assert_eq!(fields, vec![(0, "start"), (6, "A")]);
+    }
+
+    #[test]
+    fn scan_errors() {
+        let e = scan!("{},{} -> {},{}", "0,9 => 5,9" => i64, i64, i64, i64).unwrap_err();
+        assert_eq!((e.pos, e.msg.as_str()), (2, "expected \" -> \""));
+
+        let e = scan!("{},{}", "3,x" => i64, i64).unwrap_err();
+        assert_eq!(e.pos, 2);
+        assert!(e.msg.starts_with("invalid field \"x\""));
+
+        let e = scan!("{}-{}", "-end" => String, String).unwrap_err();
+        assert_eq!((e.pos, e.msg.as_str()), (0, "empty field"));
+    }
+
+    #[test]
+    fn extract_ints() {
+        let s = "target area: x=20..30, y=-10..-5";
+        assert_eq!(s.ints(), Ok(vec![20, 30, -10, -5]));
+        assert_eq!("2021-12-01".ints(), Ok(vec![2021, 12, 1]));
+        assert_eq!("fold along y=7".ints_n::<1>(), Ok([7]));
+        assert_eq!(
+            "0,9 -> 5,9\n8,0 -> 0,8"
+                .as_bytes()
+                .read_ints()
+                .unwrap()
+                .len(),
+            8
+        );
+
+        let e = "1,2,3".ints_n::<2>().unwrap_err();
+        assert_eq!(e.msg, "expected 2 integers, found 3");
+
+        let e = "x=1, y=-12345678901234567890".ints_n::<2>().unwrap_err();
+        assert_eq!(e.pos, 7);
+        assert!(e.msg.starts_with("invalid integer"));
+    }
+
+    #[test]
+    fn sections() {
+        let input = "7,4,9\n\nab\ncd\n\n1\n\n2\n";
+        let mut s = Sections::from_reader(input.as_bytes());
+        assert_eq!(s.next_commas::<u8>(), Ok(vec![7, 4, 9]));
+        assert_eq!(s.next_lines::<String>().unwrap(), vec!["ab", "cd"]);
+        assert_eq!(s.rest_as::<Vec<u32>>(), Ok(vec![1, 2]));
+        assert_eq!(s.next_section::<u32>(), Err(SectionError::Missing(4)));
+
+        let mut s = Sections::from_reader("1\n\n2\nx\n".as_bytes());
+        s.next_section::<u32>().unwrap();
+        let e = s.next_lines::<u32>().unwrap_err();
+        assert!(matches!(e, SectionError::Invalid { index: 1, .. }));
+        assert!(e
+            .to_string()
+            .starts_with("invalid section 1: line 1 (\"x\")"));
+    }
+
+    #[test]
+    fn fallible_lines() {
+        let r = "1\n2\nx\n4\n".as_bytes().try_lines::<u8>();
+        assert!(r.unwrap_err().starts_with("line 2 (\"x\")"));
+        assert_eq!("1\n2\n".as_bytes().try_lines::<u8>(), Ok(vec![1, 2]));
+    }
+
+    #[test]
+    fn lazy_readers() {
+        let mut lines = "1\n2\nx\n3\n".as_bytes().lines_iter::<u32>();
+        assert_eq!(lines.next(), Some(1));
+        assert_eq!(lines.collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!("3,4,5\n".as_bytes().split_commas::<u8>(), vec![3, 4, 5]);
+
+        let groups: Vec<String> = "a\nb\n\nc\n".as_bytes().split_groups();
+        assert_eq!(groups, vec!["a\nb", "c"]);
+        let mut groups = "1\n\n2\n\n".as_bytes().groups_iter::<String>();
+        assert_eq!(groups.next().as_deref(), Some("1"));
+        assert_eq!(groups.next().as_deref(), Some("2"));
+        assert_eq!(groups.next().as_deref(), Some(""));
+        assert_eq!(groups.next(), None);
     }
 }
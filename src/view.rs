@@ -0,0 +1,178 @@
+use crate::grid::{Coord, Grid, Rect};
+
+// borrowed rectangular part of a grid, coordinates are relative to the top left of the view
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    rect: Rect,
+}
+
+// derived impls would require `T: Clone`
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+// mutable counterpart of `GridView`
+#[derive(Debug)]
+pub struct GridViewMut<'a, T> {
+    cells: &'a mut [T],
+    stride: usize,
+    rect: Rect,
+}
+
+fn fits(rect: &Rect, w: usize, h: usize) -> bool {
+    rect.x + rect.w <= w && rect.y + rect.h <= h
+}
+
+#[allow(unused)]
+impl<T> Grid<T> {
+    // view on the given rectangle, if it fits in the grid
+    pub fn view(&self, rect: Rect) -> Option<GridView<'_, T>> {
+        fits(&rect, self.w, self.h).then_some(GridView { grid: self, rect })
+    }
+
+    pub fn view_mut(&mut self, rect: Rect) -> Option<GridViewMut<'_, T>> {
+        let stride = self.w;
+        fits(&rect, self.w, self.h).then_some(GridViewMut {
+            cells: self.as_slice_mut(),
+            stride,
+            rect,
+        })
+    }
+}
+
+#[allow(unused)]
+impl<'a, T> GridView<'a, T> {
+    pub fn w(&self) -> usize {
+        self.rect.w
+    }
+
+    pub fn h(&self) -> usize {
+        self.rect.h
+    }
+
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    pub fn get(&self, c: impl Coord) -> Option<&'a T> {
+        if c.x() < self.rect.w && c.y() < self.rect.h {
+            self.grid.get((self.rect.x + c.x(), self.rect.y + c.y()))
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> {
+        let Rect { x, y, w, h } = self.rect;
+        let grid: &'a Grid<T> = self.grid;
+        grid.rows().skip(y).take(h).map(move |r| &r[x..x + w])
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = &'a T>> {
+        let view = *self;
+        (0..self.rect.w).map(move |x| (0..view.rect.h).flat_map(move |y| view.get((x, y))))
+    }
+
+    pub fn enumerate_cells(&self) -> impl Iterator<Item = ((usize, usize), &'a T)> {
+        self.rows()
+            .enumerate()
+            .flat_map(|(y, r)| r.iter().enumerate().map(move |(x, c)| ((x, y), c)))
+    }
+
+    pub fn to_grid(self) -> Grid<T>
+    where
+        T: Clone + Default,
+    {
+        let mut g = Grid::new(self.rect.w, self.rect.h);
+        for (dst, src) in g.iter_mut().zip(self.rows().flatten()) {
+            *dst = src.clone();
+        }
+        g
+    }
+}
+
+#[allow(unused)]
+impl<'a, T> GridViewMut<'a, T> {
+    pub fn w(&self) -> usize {
+        self.rect.w
+    }
+
+    pub fn h(&self) -> usize {
+        self.rect.h
+    }
+
+    fn index(&self, c: &impl Coord) -> Option<usize> {
+        if c.x() < self.rect.w && c.y() < self.rect.h {
+            Some((self.rect.y + c.y()) * self.stride + self.rect.x + c.x())
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, c: impl Coord) -> Option<&T> {
+        self.index(&c).and_then(|i| self.cells.get(i))
+    }
+
+    pub fn get_mut(&mut self, c: impl Coord) -> Option<&mut T> {
+        self.index(&c).and_then(move |i| self.cells.get_mut(i))
+    }
+
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        let Rect { x, y, w, h } = self.rect;
+        self.cells
+            .chunks_mut(self.stride.max(1))
+            .skip(y)
+            .take(h)
+            .map(move |r| &mut r[x..x + w])
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.rows_mut().flatten()
+    }
+
+    pub fn fill(&mut self, val: T)
+    where
+        T: Clone,
+    {
+        for c in self.iter_mut() {
+            *c = val.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<u8> {
+        Grid::from_reader_callback("1234\n5678\n9012".as_bytes(), |b| Ok::<_, ()>(b - b'0'))
+            .unwrap()
+    }
+
+    #[test]
+    fn view() {
+        let g = grid();
+        let v = g.view(Rect::new(1, 1, 2, 2)).unwrap();
+        assert_eq!(v.get((1, 1)), Some(&1));
+        assert_eq!(v.get((2, 0)), None);
+        assert_eq!(v.rows().collect::<Vec<_>>(), vec![&[6, 7][..], &[0, 1][..]]);
+        assert_eq!(v.cols().nth(1).unwrap().collect::<Vec<_>>(), vec![&7, &1]);
+        assert!(g.view(Rect::new(3, 0, 2, 1)).is_none());
+    }
+
+    #[test]
+    fn view_mut() {
+        let mut g = grid();
+        let mut v = g.view_mut(Rect::new(2, 0, 2, 3)).unwrap();
+        v.fill(0);
+        if let Some(c) = v.get_mut((0, 1)) {
+            *c = 9;
+        }
+        assert_eq!(g.as_slice(), &[1, 2, 0, 0, 5, 6, 9, 0, 9, 0, 0, 0]);
+    }
+}